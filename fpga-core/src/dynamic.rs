@@ -0,0 +1,212 @@
+//! Object-safe companions of the platform traits.
+//!
+//! [Platform] and its capability traits have associated types and generic
+//! methods, which means they can't be used as trait objects. The traits in
+//! this module erase those types, so platforms of different backends can be
+//! selected at runtime and handled as `Box<dyn DynPlatform>`. Backend errors
+//! are converted to [Error].
+
+use crate::{Context, Error, Platform, PlatformType, Power, Program, Thermal, MMIO};
+use std::any::Any;
+
+/// Object-safe version of [Platform], implemented for every [Platform].
+pub trait DynPlatform {
+    /// Returns specific [PlatformType] of the underlying platform.
+    fn platform_type(&self) -> PlatformType;
+
+    /// Returns the underlying platform as [Any], to downcast to a concrete type.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Returns the underlying platform as [Any], to downcast to a concrete type.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// Returns the underlying platform if it supports [MMIO].
+    fn as_mmio(&self) -> Option<&dyn DynMMIO>;
+
+    /// Returns the underlying platform if it supports [MMIO].
+    fn as_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO>;
+
+    /// Returns the underlying platform if it supports [Power].
+    fn as_power(&self) -> Option<&dyn DynPower>;
+
+    /// Returns the underlying platform if it supports [Thermal].
+    fn as_thermal(&self) -> Option<&dyn DynThermal>;
+
+    /// Returns the underlying platform if it supports [Program].
+    fn as_program(&mut self) -> Option<&mut dyn DynProgram>;
+}
+
+impl<T> DynPlatform for T
+where
+    T: Platform + 'static,
+{
+    fn platform_type(&self) -> PlatformType {
+        self.platform()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn as_mmio(&self) -> Option<&dyn DynMMIO> {
+        self.dyn_mmio()
+    }
+
+    fn as_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        self.dyn_mmio_mut()
+    }
+
+    fn as_power(&self) -> Option<&dyn DynPower> {
+        self.dyn_power()
+    }
+
+    fn as_thermal(&self) -> Option<&dyn DynThermal> {
+        self.dyn_thermal()
+    }
+
+    fn as_program(&mut self) -> Option<&mut dyn DynProgram> {
+        self.dyn_program()
+    }
+}
+
+/// Object-safe version of [MMIO], implemented for every [MMIO]. The methods
+/// are named differently from those of [MMIO] to avoid ambiguity when both
+/// traits are in scope.
+pub trait DynMMIO {
+    /// See [MMIO::num_spaces].
+    fn mmio_spaces(&self) -> u32;
+
+    /// See [MMIO::space_size].
+    fn mmio_space_size(&self, space: u32) -> Option<usize>;
+
+    /// See [MMIO::read_u32].
+    fn load_u32(&self, space: u32, offset: usize) -> Result<u32, Error>;
+
+    /// See [MMIO::read_u64].
+    fn load_u64(&self, space: u32, offset: usize) -> Result<u64, Error>;
+
+    /// See [MMIO::write_u32].
+    fn store_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<(), Error>;
+
+    /// See [MMIO::write_u64].
+    fn store_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<(), Error>;
+
+    /// See [MMIO::read_into].
+    fn load_into(&self, space: u32, offset: usize, data: &mut [u32]) -> Result<(), Error>;
+
+    /// See [MMIO::write_from].
+    fn store_from(&mut self, space: u32, offset: usize, data: &[u32]) -> Result<(), Error>;
+}
+
+impl<T> DynMMIO for T
+where
+    T: MMIO,
+    T::Error: Into<Error>,
+{
+    fn mmio_spaces(&self) -> u32 {
+        self.num_spaces()
+    }
+
+    fn mmio_space_size(&self, space: u32) -> Option<usize> {
+        self.space_size(space)
+    }
+
+    fn load_u32(&self, space: u32, offset: usize) -> Result<u32, Error> {
+        self.read_u32(space, offset)
+            .map_err(|error| error.into().with_operation("read_u32"))
+    }
+
+    fn load_u64(&self, space: u32, offset: usize) -> Result<u64, Error> {
+        self.read_u64(space, offset)
+            .map_err(|error| error.into().with_operation("read_u64"))
+    }
+
+    fn store_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<(), Error> {
+        self.write_u32(space, offset, value)
+            .map_err(|error| error.into().with_operation("write_u32"))
+    }
+
+    fn store_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<(), Error> {
+        self.write_u64(space, offset, value)
+            .map_err(|error| error.into().with_operation("write_u64"))
+    }
+
+    fn load_into(&self, space: u32, offset: usize, data: &mut [u32]) -> Result<(), Error> {
+        self.read_into(space, offset, data)
+            .map_err(|error| error.into().with_operation("read_into"))
+    }
+
+    fn store_from(&mut self, space: u32, offset: usize, data: &[u32]) -> Result<(), Error> {
+        self.write_from(space, offset, data)
+            .map_err(|error| error.into().with_operation("write_from"))
+    }
+}
+
+/// Object-safe version of [Power], implemented for every [Power].
+pub trait DynPower {
+    /// See [Power::power]. Errors are converted into [Error] with the
+    /// `power` operation.
+    fn current_power(&self) -> Result<f32, Error>;
+}
+
+impl<T> DynPower for T
+where
+    T: Power,
+    T::Error: Into<Error>,
+{
+    fn current_power(&self) -> Result<f32, Error> {
+        self.power()
+            .map_err(|error| error.into().with_operation("power"))
+    }
+}
+
+/// Object-safe version of [Thermal], implemented for every [Thermal].
+pub trait DynThermal {
+    /// See [Thermal::temperature]. Errors are converted into [Error] with the
+    /// `temperature` operation.
+    fn current_temperature(&self) -> Result<f32, Error>;
+}
+
+impl<T> DynThermal for T
+where
+    T: Thermal,
+    T::Error: Into<Error>,
+{
+    fn current_temperature(&self) -> Result<f32, Error> {
+        self.temperature()
+            .map_err(|error| error.into().with_operation("temperature"))
+    }
+}
+
+/// Object-safe version of [Program], implemented for every [Program].
+pub trait DynProgram {
+    /// Programs the platform. The `source` must be the [Program::Source] of
+    /// the platform, otherwise [Error::InvalidParam] is returned. The returned
+    /// value is the boxed [Program::Output].
+    fn program_any(&mut self, source: Box<dyn Any>) -> Result<Box<dyn Any>, Error>;
+}
+
+impl<T> DynProgram for T
+where
+    T: Program,
+    T::Source: 'static,
+    T::Output: 'static,
+    T::Error: Into<Error>,
+{
+    fn program_any(&mut self, source: Box<dyn Any>) -> Result<Box<dyn Any>, Error> {
+        let source = source.downcast::<T::Source>().map_err(|_| {
+            Error::InvalidParam(
+                Context::new()
+                    .with_operation("program")
+                    .with_source("invalid source type for platform"),
+            )
+        })?;
+        self.program(*source)
+            .map(|output| Box::new(output) as Box<dyn Any>)
+            .map_err(|error| error.into().with_operation("program"))
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+};
+
+/// Context of an [Error]: the failed operation, the device it was performed
+/// on and the underlying backend error.
+#[derive(Debug, Default)]
+pub struct Context {
+    pub operation: Option<String>,
+    pub device: Option<String>,
+    pub source: Option<Box<dyn StdError + Send + Sync>>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_operation<T>(mut self, operation: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.operation.replace(operation.into());
+        self
+    }
+
+    pub fn with_device<T>(mut self, device: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.device.replace(device.into());
+        self
+    }
+
+    pub fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        self.source.replace(source.into());
+        self
+    }
+}
+
+/// Platform error shared by all backends. Backend errors convert into this
+/// type, which categorizes them so callers can decide to e.g. retry or fail
+/// over to another device without inspecting backend specific errors.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum Error {
+    /// The requested device or resource does not exist.
+    NotFound(Context),
+    /// The device or resource is in use.
+    Busy(Context),
+    /// An invalid parameter was provided.
+    InvalidParam(Context),
+    /// Insufficient permissions to access the device or resource.
+    PermissionDenied(Context),
+    /// The operation did not complete in time.
+    Timeout(Context),
+    /// The operation is not supported by the platform.
+    Unsupported(Context),
+    /// Any other backend error.
+    Backend(Context),
+}
+
+impl Error {
+    /// Returns a [Error::Backend] error with `source` as underlying error.
+    pub fn backend<E>(source: E) -> Self
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        Self::Backend(Context::new().with_source(source))
+    }
+
+    pub fn context(&self) -> &Context {
+        match self {
+            Self::NotFound(context)
+            | Self::Busy(context)
+            | Self::InvalidParam(context)
+            | Self::PermissionDenied(context)
+            | Self::Timeout(context)
+            | Self::Unsupported(context)
+            | Self::Backend(context) => context,
+        }
+    }
+
+    fn context_mut(&mut self) -> &mut Context {
+        match self {
+            Self::NotFound(context)
+            | Self::Busy(context)
+            | Self::InvalidParam(context)
+            | Self::PermissionDenied(context)
+            | Self::Timeout(context)
+            | Self::Unsupported(context)
+            | Self::Backend(context) => context,
+        }
+    }
+
+    /// Sets the operation that caused this error.
+    pub fn with_operation<T>(mut self, operation: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.context_mut().operation.replace(operation.into());
+        self
+    }
+
+    /// Sets the device on which this error occurred.
+    pub fn with_device<T>(mut self, device: T) -> Self
+    where
+        T: Into<String>,
+    {
+        self.context_mut().device.replace(device.into());
+        self
+    }
+
+    /// Returns true if the failed operation may succeed when retried.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Busy(_) | Self::Timeout(_))
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Self::NotFound(_) => "not found",
+            Self::Busy(_) => "busy",
+            Self::InvalidParam(_) => "invalid parameter",
+            Self::PermissionDenied(_) => "permission denied",
+            Self::Timeout(_) => "timeout",
+            Self::Unsupported(_) => "unsupported",
+            Self::Backend(_) => "backend error",
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.context()
+            .source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let context = self.context();
+        write!(f, "{}", self.description())?;
+        if let Some(ref operation) = context.operation {
+            write!(f, " during {}", operation)?;
+        }
+        if let Some(ref device) = context.device {
+            write!(f, " on {}", device)?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors of accesses to memory-mapped address spaces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MmioError {
+    /// The address space does not exist.
+    InvalidSpace { space: u32, num_spaces: u32 },
+    /// The offset is not aligned to the width of the access.
+    Misaligned { offset: usize, align: usize },
+    /// The access of `len` bytes at `offset` exceeds the address space of
+    /// `size` bytes.
+    OutOfBounds {
+        offset: usize,
+        len: usize,
+        size: usize,
+    },
+}
+
+impl StdError for MmioError {}
+
+impl Display for MmioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSpace { space, num_spaces } => write!(
+                f,
+                "address space {} does not exist, number of address spaces is {}",
+                space, num_spaces
+            ),
+            Self::Misaligned { offset, align } => {
+                write!(f, "offset {:#x} is not aligned to {} bytes", offset, align)
+            }
+            Self::OutOfBounds { offset, len, size } => write!(
+                f,
+                "access of {} bytes at offset {:#x} exceeds address space of {} bytes",
+                len, offset, size
+            ),
+        }
+    }
+}
+
+impl From<MmioError> for Error {
+    fn from(error: MmioError) -> Self {
+        Self::InvalidParam(Context::new().with_source(error))
+    }
+}
@@ -1,13 +1,20 @@
 use std::{
-    error::Error,
+    error::Error as StdError,
     fmt::{self, Display, Formatter},
 };
 
+mod dynamic;
+pub use dynamic::*;
+
+mod errors;
+pub use errors::*;
+
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PlatformType {
     XRT,
     OPAE,
+    SIM,
 }
 
 impl Display for PlatformType {
@@ -18,6 +25,7 @@ impl Display for PlatformType {
             match self {
                 Self::XRT => "xrt",
                 Self::OPAE => "opae",
+                Self::SIM => "sim",
             }
         )
     }
@@ -28,7 +36,7 @@ pub trait Platform: Sized {
     type Configuration;
 
     /// Platform specific error type.
-    type Error: Error;
+    type Error: StdError;
 
     /// Returns specific [PlatformType]. Can be used when performing platform
     /// tasks e.g. selecting a bit stream compatible with this platform. Can
@@ -37,26 +45,136 @@ pub trait Platform: Sized {
 
     /// Constructs and initializes this platform using the provided platform configuration.
     fn from_configuration(configuration: Self::Configuration) -> Result<Self, Self::Error>;
+
+    /// Returns this platform as [DynMMIO]. Platforms that implement [MMIO]
+    /// should override this to return `Some(self)`.
+    fn dyn_mmio(&self) -> Option<&dyn DynMMIO> {
+        None
+    }
+
+    /// Returns this platform as [DynMMIO]. Platforms that implement [MMIO]
+    /// should override this to return `Some(self)`.
+    fn dyn_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        None
+    }
+
+    /// Returns this platform as [DynPower]. Platforms that implement [Power]
+    /// should override this to return `Some(self)`.
+    fn dyn_power(&self) -> Option<&dyn DynPower> {
+        None
+    }
+
+    /// Returns this platform as [DynThermal]. Platforms that implement
+    /// [Thermal] should override this to return `Some(self)`.
+    fn dyn_thermal(&self) -> Option<&dyn DynThermal> {
+        None
+    }
+
+    /// Returns this platform as [DynProgram]. Platforms that implement
+    /// [Program] should override this to return `Some(self)`.
+    fn dyn_program(&mut self) -> Option<&mut dyn DynProgram> {
+        None
+    }
 }
 
+/// Register access on memory-mapped address spaces of a platform, e.g. PCIe
+/// BARs or MMIO regions of an accelerator.
+///
+/// Offsets are in bytes from the start of an address space and must be
+/// aligned to the width of the access.
 pub trait MMIO: Platform {
-    fn read_mmio<const N: usize>(&self, offset: usize, len: usize) -> Result<[u8; N], Self::Error>;
-    fn write_mmio<T>(&mut self, offset: usize, data: T) -> Result<(), Self::Error>
-    where
-        T: AsRef<[u8]>;
+    /// Returns the number of address spaces.
+    fn num_spaces(&self) -> u32;
+
+    /// Returns the size in bytes of address space `space`, if known.
+    fn space_size(&self, _space: u32) -> Option<usize> {
+        None
+    }
+
+    /// Reads the 32-bit register at `offset` in address space `space`.
+    fn read_u32(&self, space: u32, offset: usize) -> Result<u32, Self::Error>;
+
+    /// Reads the 64-bit register at `offset` in address space `space`.
+    fn read_u64(&self, space: u32, offset: usize) -> Result<u64, Self::Error>;
+
+    /// Writes `value` to the 32-bit register at `offset` in address space
+    /// `space`.
+    fn write_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<(), Self::Error>;
+
+    /// Writes `value` to the 64-bit register at `offset` in address space
+    /// `space`.
+    fn write_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<(), Self::Error>;
+
+    /// Reads consecutive 32-bit registers starting at `offset` in address
+    /// space `space` into `data`.
+    ///
+    /// The default implementation reads the registers one by one. Platforms
+    /// that know the size of their address spaces should check the full
+    /// range before accessing it.
+    fn read_into(&self, space: u32, offset: usize, data: &mut [u32]) -> Result<(), Self::Error> {
+        data.iter_mut().enumerate().try_for_each(|(index, value)| {
+            *value = self.read_u32(space, offset + index * 4)?;
+            Ok(())
+        })
+    }
+
+    /// Writes `data` to consecutive 32-bit registers starting at `offset` in
+    /// address space `space`.
+    ///
+    /// The default implementation writes the registers one by one, which
+    /// means a failing access can result in a partial write. Platforms that
+    /// know the size of their address spaces should check the full range
+    /// before accessing it.
+    fn write_from(&mut self, space: u32, offset: usize, data: &[u32]) -> Result<(), Self::Error> {
+        data.iter()
+            .enumerate()
+            .try_for_each(|(index, &value)| self.write_u32(space, offset + index * 4, value))
+    }
+
+    /// Checks an access of `len` bytes at `offset` in address space `space`
+    /// with required alignment `align` (a power of two), against the number of address spaces
+    /// and their size if known.
+    fn check_access(
+        &self,
+        space: u32,
+        offset: usize,
+        len: usize,
+        align: usize,
+    ) -> Result<(), MmioError> {
+        let num_spaces = self.num_spaces();
+        if space >= num_spaces {
+            return Err(MmioError::InvalidSpace { space, num_spaces });
+        }
+        if offset & (align - 1) != 0 {
+            return Err(MmioError::Misaligned { offset, align });
+        }
+        match self.space_size(space) {
+            Some(size) if offset.checked_add(len).filter(|&end| end <= size).is_none() => {
+                Err(MmioError::OutOfBounds { offset, len, size })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
+/// Power sensors of a platform. Reading a sensor can fail, e.g. when the
+/// device is offline or does not report its power usage, in which case an
+/// error of the platform is returned.
 pub trait Power: Platform {
     /// Current power usage in Watts.
-    fn power(&self) -> f32;
+    fn power(&self) -> Result<f32, Self::Error>;
 }
 
+/// Temperature sensors of a platform. Reading a sensor can fail, e.g. when
+/// the device is offline or has no temperature sensor, in which case an error
+/// of the platform is returned.
 pub trait Thermal: Platform {
     /// Current temperature of device in degrees C.
-    fn temperature(&self) -> f32;
+    fn temperature(&self) -> Result<f32, Self::Error>;
     /// Current temperature of device in degrees F.
-    fn temperature_f(&self) -> f32 {
-        (self.temperature() * 9. / 5.) + 32.
+    fn temperature_f(&self) -> Result<f32, Self::Error> {
+        self.temperature()
+            .map(|temperature| (temperature * 9. / 5.) + 32.)
     }
 }
 
@@ -0,0 +1,34 @@
+/// Errors returned when parsing a GBS file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with the GBS GUID.
+    InvalidGuid,
+    /// A structure at `offset` of `len` bytes exceeds the data of `size`
+    /// bytes.
+    Truncated {
+        offset: usize,
+        len: usize,
+        size: usize,
+    },
+    /// The JSON metadata is malformed.
+    InvalidMetadata(String),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidGuid => write!(f, "invalid GBS GUID"),
+            Self::Truncated { offset, len, size } => write!(
+                f,
+                "structure of {} bytes at offset {:#x} exceeds GBS of {} bytes",
+                len, offset, size
+            ),
+            Self::InvalidMetadata(message) => write!(f, "invalid GBS metadata: {}", message),
+        }
+    }
+}
+
+/// Result type with `Error` error type.
+pub type Result<T> = std::result::Result<T, Error>;
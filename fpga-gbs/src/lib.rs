@@ -0,0 +1,201 @@
+//! Pure-Rust parser of OPAE green bitstream (GBS) files.
+//!
+//! A GBS file consists of a GUID header, the length of the JSON metadata, the
+//! JSON metadata and the partial bitstream, and can be inspected without
+//! requiring OPAE to be installed.
+
+use serde::Deserialize;
+use std::convert::TryInto;
+use uuid::Uuid;
+
+mod errors;
+pub use errors::*;
+
+/// GUID at the start of every GBS file.
+pub const GUID: Uuid = Uuid::from_bytes(*b"XeonFPGA\xb7GBSv001");
+
+/// Offset of the metadata length in a GBS file.
+const METADATA_LENGTH_OFFSET: usize = 16;
+
+/// Offset of the metadata in a GBS file.
+const METADATA_OFFSET: usize = METADATA_LENGTH_OFFSET + 4;
+
+/// The JSON metadata of a GBS file.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Metadata {
+    pub version: u32,
+    pub afu_image: AfuImage,
+    /// Name of the platform the GBS was built for.
+    pub platform_name: Option<String>,
+}
+
+/// The AFU image of the metadata of a GBS file.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AfuImage {
+    pub magic_no: Option<u64>,
+    /// UUID of the PR interface of the FIM the GBS targets.
+    pub interface_uuid: Uuid,
+    /// Power in Watts.
+    pub power: Option<f64>,
+    /// Frequency of the high user clock in MHz.
+    pub clock_frequency_high: Option<f64>,
+    /// Frequency of the low user clock in MHz.
+    pub clock_frequency_low: Option<f64>,
+    #[serde(default)]
+    pub accelerator_clusters: Vec<AcceleratorCluster>,
+}
+
+/// An accelerator of the AFU image of a GBS file.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AcceleratorCluster {
+    pub name: String,
+    pub total_contexts: u32,
+    /// GUID of the accelerator after reconfiguration.
+    pub accelerator_type_uuid: Uuid,
+}
+
+/// A parsed GBS file, borrowing the underlying data.
+#[derive(Clone, Debug)]
+pub struct Gbs<'data> {
+    metadata: Metadata,
+    json: &'data str,
+    bitstream: &'data [u8],
+}
+
+impl<'data> Gbs<'data> {
+    /// Parses the header and metadata of the GBS file in `data`.
+    pub fn parse(data: &'data [u8]) -> Result<Self> {
+        let bytes = |offset: usize, len: usize| {
+            offset
+                .checked_add(len)
+                .and_then(|end| data.get(offset..end))
+                .ok_or(Error::Truncated {
+                    offset,
+                    len,
+                    size: data.len(),
+                })
+        };
+
+        if bytes(0, METADATA_LENGTH_OFFSET)? != GUID.as_bytes() {
+            return Err(Error::InvalidGuid);
+        }
+        let len = u32::from_le_bytes(bytes(METADATA_LENGTH_OFFSET, 4)?.try_into().unwrap());
+        let json = std::str::from_utf8(bytes(METADATA_OFFSET, len as usize)?)
+            .map_err(|error| Error::InvalidMetadata(error.to_string()))?;
+        let metadata = serde_json::from_str(json)
+            .map_err(|error| Error::InvalidMetadata(error.to_string()))?;
+
+        Ok(Self {
+            metadata,
+            json,
+            bitstream: &data[METADATA_OFFSET + len as usize..],
+        })
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the JSON of the metadata.
+    pub fn json(&self) -> &'data str {
+        self.json
+    }
+
+    /// Returns the UUID of the PR interface this GBS targets.
+    pub fn interface_uuid(&self) -> Uuid {
+        self.metadata.afu_image.interface_uuid
+    }
+
+    /// Returns the GUID of the first accelerator of this GBS, if any.
+    pub fn accelerator_uuid(&self) -> Option<Uuid> {
+        self.metadata
+            .afu_image
+            .accelerator_clusters
+            .first()
+            .map(|cluster| cluster.accelerator_type_uuid)
+    }
+
+    /// Returns the partial bitstream.
+    pub fn bitstream(&self) -> &'data [u8] {
+        self.bitstream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a GBS file with `json` metadata and `bitstream`.
+    fn gbs(json: &str, bitstream: &[u8]) -> Vec<u8> {
+        let mut data = GUID.as_bytes().to_vec();
+        data.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        data.extend_from_slice(json.as_bytes());
+        data.extend_from_slice(bitstream);
+        data
+    }
+
+    #[test]
+    fn parse() {
+        let json = r#"{
+            "version": 1,
+            "platform-name": "pac_a10",
+            "afu-image": {
+                "magic-no": 488605312,
+                "interface-uuid": "9926ab6d-6c92-5a68-aabc-a7d84c545738",
+                "power": 0,
+                "clock-frequency-high": 312.5,
+                "clock-frequency-low": 156.25,
+                "accelerator-clusters": [{
+                    "name": "nlb_400",
+                    "total-contexts": 1,
+                    "accelerator-type-uuid": "d8424dc4-a4a3-c413-f89e-433683f9040b"
+                }]
+            }
+        }"#;
+        let data = gbs(json, &[0xff; 4]);
+        let gbs = Gbs::parse(&data).unwrap();
+
+        assert_eq!(gbs.json(), json);
+        assert_eq!(gbs.bitstream(), &[0xff; 4]);
+        assert_eq!(gbs.metadata().version, 1);
+        assert_eq!(gbs.metadata().platform_name.as_deref(), Some("pac_a10"));
+        assert_eq!(
+            gbs.interface_uuid(),
+            Uuid::parse_str("9926ab6d-6c92-5a68-aabc-a7d84c545738").unwrap()
+        );
+        assert_eq!(
+            gbs.accelerator_uuid(),
+            Some(Uuid::parse_str("d8424dc4-a4a3-c413-f89e-433683f9040b").unwrap())
+        );
+        let afu_image = &gbs.metadata().afu_image;
+        assert_eq!(afu_image.clock_frequency_high, Some(312.5));
+        assert_eq!(afu_image.accelerator_clusters[0].name, "nlb_400");
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Gbs::parse(&[0; 20]).unwrap_err(), Error::InvalidGuid);
+        assert!(matches!(
+            Gbs::parse(GUID.as_bytes()).unwrap_err(),
+            Error::Truncated { .. }
+        ));
+
+        // Metadata that exceeds the data.
+        let mut data = gbs("{}", &[]);
+        data.truncate(data.len() - 1);
+        assert!(matches!(
+            Gbs::parse(&data).unwrap_err(),
+            Error::Truncated { .. }
+        ));
+
+        // Metadata without AFU image.
+        let data = gbs(r#"{ "version": 1 }"#, &[]);
+        assert!(matches!(
+            Gbs::parse(&data).unwrap_err(),
+            Error::InvalidMetadata(_)
+        ));
+    }
+}
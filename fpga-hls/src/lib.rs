@@ -0,0 +1,316 @@
+//! Driver for the block-level control protocol of Vitis HLS kernels.
+//!
+//! HLS kernels with an `s_axilite` control interface expose the `ap_ctrl_hs`
+//! or `ap_ctrl_chain` protocol through a set of registers at fixed offsets:
+//! the control register at `0x00`, the global interrupt enable at `0x04`, the
+//! interrupt enable register at `0x08`, the interrupt status register at
+//! `0x0c` and the argument registers starting at `0x10`. The [Driver]
+//! implements this protocol on top of any [MMIO] implementation.
+
+use fpga_core::MMIO;
+use fpga_regs::RegisterBlock;
+use std::{
+    hint,
+    time::{Duration, Instant},
+};
+
+mod registers;
+pub use registers::*;
+
+/// Block-level control protocol of an HLS kernel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    /// `ap_ctrl_hs`: the kernel is started and signals completion.
+    Hs,
+    /// `ap_ctrl_chain`: as `ap_ctrl_hs`, but the completion must be
+    /// acknowledged with `ap_continue` before the kernel completes again.
+    Chain,
+}
+
+/// Driver of an HLS kernel at `base` in an address space.
+#[derive(Debug)]
+pub struct Driver {
+    registers: Registers,
+    space: u32,
+    base: usize,
+    protocol: Protocol,
+}
+
+impl Driver {
+    /// Returns a driver for the kernel with control protocol `protocol` at
+    /// `base` in address space `space`.
+    pub fn new(space: u32, base: usize, protocol: Protocol) -> Self {
+        Self {
+            registers: Registers::at(space, base),
+            space,
+            base,
+            protocol,
+        }
+    }
+
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    /// Returns the status of the kernel.
+    ///
+    /// Reading the status clears the done bit of the kernel, so a completion
+    /// is reported only once.
+    pub fn status<M>(&self, mmio: &M) -> Result<Status, M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers.ctrl.read(mmio).map(Status::from)
+    }
+
+    /// Starts the kernel, preserving the auto-restart setting.
+    pub fn start<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers
+            .ctrl
+            .modify(mmio, |value| (value & ctrl::AUTO_RESTART) | ctrl::AP_START)
+    }
+
+    /// Enables or disables automatically restarting the kernel when it
+    /// completes. This does not start the kernel.
+    pub fn set_auto_restart<M>(&self, mmio: &mut M, enable: bool) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers
+            .ctrl
+            .write(mmio, if enable { ctrl::AUTO_RESTART } else { 0 })
+    }
+
+    /// Acknowledges the completion of an `ap_ctrl_chain` kernel. Does nothing
+    /// for `ap_ctrl_hs` kernels.
+    pub fn acknowledge<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        match self.protocol {
+            Protocol::Hs => Ok(()),
+            Protocol::Chain => self.registers.ctrl.modify(mmio, |value| {
+                (value & ctrl::AUTO_RESTART) | ctrl::AP_CONTINUE
+            }),
+        }
+    }
+
+    /// Polls the kernel until it completes, and acknowledges the completion
+    /// for `ap_ctrl_chain` kernels.
+    pub fn wait<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        while !self.status(mmio)?.done {
+            hint::spin_loop();
+        }
+        self.acknowledge(mmio)
+    }
+
+    /// Polls the kernel until it completes or `timeout` expires. Returns
+    /// false if the kernel did not complete in time.
+    pub fn wait_timeout<M>(&self, mmio: &mut M, timeout: Duration) -> Result<bool, M::Error>
+    where
+        M: MMIO,
+    {
+        let start = Instant::now();
+        loop {
+            if self.status(mmio)?.done {
+                return self.acknowledge(mmio).map(|_| true);
+            }
+            if start.elapsed() >= timeout {
+                return Ok(false);
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Starts the kernel and waits for it to complete.
+    pub fn run<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.start(mmio)?;
+        self.wait(mmio)
+    }
+
+    /// Enables the global interrupt and the interrupt events in `interrupts`,
+    /// disabling all other events.
+    pub fn enable_interrupts<M>(&self, mmio: &mut M, interrupts: Interrupts) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers.ier.write(mmio, interrupts.bits())?;
+        self.registers.gie.write(mmio, 1)
+    }
+
+    /// Disables the global interrupt and all interrupt events.
+    pub fn disable_interrupts<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers.gie.write(mmio, 0)?;
+        self.registers.ier.write(mmio, 0)
+    }
+
+    /// Returns the pending interrupt events.
+    pub fn interrupt_status<M>(&self, mmio: &M) -> Result<Interrupts, M::Error>
+    where
+        M: MMIO,
+    {
+        self.registers.isr.read(mmio).map(Interrupts::from)
+    }
+
+    /// Clears the pending interrupt events in `interrupts`.
+    pub fn clear_interrupts<M>(&self, mmio: &mut M, interrupts: Interrupts) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        // The status register is toggle-on-write, so only write pending bits.
+        let pending = self.registers.isr.read(mmio)?;
+        match pending & interrupts.bits() {
+            0 => Ok(()),
+            bits => self.registers.isr.write(mmio, bits),
+        }
+    }
+
+    /// Writes the 32-bit argument register at `offset`, relative to the base
+    /// of the kernel.
+    pub fn write_arg_u32<M>(&self, mmio: &mut M, offset: usize, value: u32) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.write_u32(self.space, self.base + offset, value)
+    }
+
+    /// Writes the 64-bit argument at `offset`, relative to the base of the
+    /// kernel. The control interface is 32 bits wide, so the low word is
+    /// written at `offset` and the high word at `offset + 4`.
+    pub fn write_arg_u64<M>(&self, mmio: &mut M, offset: usize, value: u64) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.write_arg_u32(mmio, offset, value as u32)?;
+        self.write_arg_u32(mmio, offset + 4, (value >> 32) as u32)
+    }
+
+    /// Reads the 32-bit argument register at `offset`, relative to the base
+    /// of the kernel.
+    pub fn read_arg_u32<M>(&self, mmio: &M, offset: usize) -> Result<u32, M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.read_u32(self.space, self.base + offset)
+    }
+
+    /// Reads the 64-bit argument at `offset`, relative to the base of the
+    /// kernel.
+    pub fn read_arg_u64<M>(&self, mmio: &M, offset: usize) -> Result<u64, M::Error>
+    where
+        M: MMIO,
+    {
+        let low = self.read_arg_u32(mmio, offset)?;
+        let high = self.read_arg_u32(mmio, offset + 4)?;
+        Ok(u64::from(high) << 32 | u64::from(low))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fpga_sim::Sim;
+
+    /// Returns a simulated kernel at `0x100` that adds its two arguments at
+    /// `0x10` and `0x18` into `0x20` and completes immediately when started.
+    fn kernel() -> Sim {
+        let mut sim = Sim::new();
+        sim.registers_mut().write_u32(0x100, ctrl::AP_IDLE).unwrap();
+        sim.on_write(0x100..0x104, |registers, offset, data| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(data);
+            let value = u32::from_le_bytes(bytes);
+            let mut status = (value & ctrl::AUTO_RESTART) | ctrl::AP_IDLE;
+            if value & ctrl::AP_START != 0 {
+                let a = registers.read_u32(0x110).unwrap();
+                let b = registers.read_u32(0x118).unwrap();
+                registers.write_u32(0x120, a + b).unwrap();
+                status |= ctrl::AP_DONE | ctrl::AP_READY;
+                // Raise the done interrupt if enabled.
+                if registers.read_u32(0x104).unwrap() & 1 != 0 {
+                    let ier = registers.read_u32(0x108).unwrap();
+                    registers.write_u32(0x10c, ier & 1).unwrap();
+                }
+            }
+            registers.write_u32(offset, status).unwrap();
+        });
+        // Model the toggle-on-write interrupt status register.
+        sim.on_write(0x10c..0x110, |registers, offset, data| {
+            let mut bytes = [0; 4];
+            bytes.copy_from_slice(data);
+            let value = registers.read_u32(offset).unwrap() ^ u32::from_le_bytes(bytes);
+            registers.write_u32(offset, value).unwrap();
+        });
+        sim
+    }
+
+    #[test]
+    fn run() {
+        let mut sim = kernel();
+        let driver = Driver::new(0, 0x100, Protocol::Hs);
+
+        assert!(driver.status(&sim).unwrap().idle);
+        driver.write_arg_u32(&mut sim, 0x10, 2).unwrap();
+        driver.write_arg_u32(&mut sim, 0x18, 3).unwrap();
+        driver.run(&mut sim).unwrap();
+        assert_eq!(driver.read_arg_u32(&sim, 0x20).unwrap(), 5);
+
+        driver.set_auto_restart(&mut sim, true).unwrap();
+        driver.start(&mut sim).unwrap();
+        assert!(driver.status(&sim).unwrap().auto_restart);
+        assert!(driver
+            .wait_timeout(&mut sim, Duration::from_millis(10))
+            .unwrap());
+
+        driver.write_arg_u64(&mut sim, 0x28, u64::MAX - 1).unwrap();
+        assert_eq!(driver.read_arg_u64(&sim, 0x28).unwrap(), u64::MAX - 1);
+    }
+
+    #[test]
+    fn interrupts() {
+        let mut sim = kernel();
+        let driver = Driver::new(0, 0x100, Protocol::Chain);
+
+        driver
+            .enable_interrupts(
+                &mut sim,
+                Interrupts {
+                    done: true,
+                    ready: false,
+                },
+            )
+            .unwrap();
+        driver.run(&mut sim).unwrap();
+        assert!(driver.interrupt_status(&sim).unwrap().done);
+
+        // Clearing an event that is not pending must not raise it.
+        driver.clear_interrupts(&mut sim, Interrupts::ALL).unwrap();
+        assert_eq!(
+            driver.interrupt_status(&sim).unwrap(),
+            Interrupts::default()
+        );
+
+        driver.disable_interrupts(&mut sim).unwrap();
+        driver.run(&mut sim).unwrap();
+        assert_eq!(
+            driver.interrupt_status(&sim).unwrap(),
+            Interrupts::default()
+        );
+    }
+}
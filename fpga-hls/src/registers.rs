@@ -0,0 +1,86 @@
+use fpga_regs::{Reg, RegisterBlock, RW};
+
+/// Bits of the control register.
+pub mod ctrl {
+    /// Starts the kernel. Cleared by the kernel when it accepts the start.
+    pub const AP_START: u32 = 1 << 0;
+    /// Set when the kernel completes. Cleared on read.
+    pub const AP_DONE: u32 = 1 << 1;
+    /// Set while the kernel is not processing.
+    pub const AP_IDLE: u32 = 1 << 2;
+    /// Set when the kernel is ready to accept new inputs.
+    pub const AP_READY: u32 = 1 << 3;
+    /// Acknowledges the completion of a `ap_ctrl_chain` kernel.
+    pub const AP_CONTINUE: u32 = 1 << 4;
+    /// Restarts the kernel automatically when it completes.
+    pub const AUTO_RESTART: u32 = 1 << 7;
+}
+
+/// The block-level control registers of an HLS kernel.
+///
+/// The interrupt status register is toggle-on-write: writing a 1 to a bit
+/// toggles it, so only bits that are set should be written to clear them.
+#[derive(Debug, RegisterBlock)]
+pub struct Registers {
+    #[register(offset = 0x00)]
+    pub ctrl: Reg<u32, RW>,
+    #[register(offset = 0x04)]
+    pub gie: Reg<u32, RW>,
+    #[register(offset = 0x08)]
+    pub ier: Reg<u32, RW>,
+    #[register(offset = 0x0c)]
+    pub isr: Reg<u32, RW>,
+}
+
+/// Status of an HLS kernel, decoded from its control register.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Status {
+    pub start: bool,
+    pub done: bool,
+    pub idle: bool,
+    pub ready: bool,
+    pub auto_restart: bool,
+}
+
+impl From<u32> for Status {
+    fn from(value: u32) -> Self {
+        Self {
+            start: value & ctrl::AP_START != 0,
+            done: value & ctrl::AP_DONE != 0,
+            idle: value & ctrl::AP_IDLE != 0,
+            ready: value & ctrl::AP_READY != 0,
+            auto_restart: value & ctrl::AUTO_RESTART != 0,
+        }
+    }
+}
+
+/// Interrupt events of an HLS kernel.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Interrupts {
+    /// The kernel completed.
+    pub done: bool,
+    /// The kernel is ready to accept new inputs.
+    pub ready: bool,
+}
+
+impl Interrupts {
+    /// Both interrupt events.
+    pub const ALL: Self = Self {
+        done: true,
+        ready: true,
+    };
+
+    /// Returns the bits of these events in the interrupt registers.
+    pub fn bits(self) -> u32 {
+        self.done as u32 | (self.ready as u32) << 1
+    }
+}
+
+impl From<u32> for Interrupts {
+    fn from(value: u32) -> Self {
+        Self {
+            done: value & 1 != 0,
+            ready: value & 2 != 0,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    AcceleratorProperties, Device, Filter, Properties, Resource, ResourceInfo, ResourceProperties,
-    Token,
+    AcceleratorProperties, Device, Filter, Handle, OpenMode, Properties, Resource, ResourceInfo,
+    ResourceProperties, Result, Token,
 };
 use std::fmt::Debug;
 
@@ -28,6 +28,15 @@ impl Accelerator {
         })
     }
 
+    pub(crate) fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Opens this accelerator and maps all its MMIO spaces.
+    pub fn open(&self, mode: OpenMode) -> Result<Handle> {
+        Handle::open(&self.token, mode, self.num_mmio_spaces()?)
+    }
+
     pub fn info(&self) -> AcceleratorInfo {
         AcceleratorInfo {
             resource: self.into(),
@@ -0,0 +1,102 @@
+use crate::{
+    bindings::{fpgaGetIOAddress, fpgaPrepareBuffer, fpgaReleaseBuffer, fpga_buffer_flags},
+    Handle, Result,
+};
+use log::{error, trace};
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr, slice,
+};
+
+/// Host memory pinned and mapped for DMA by an accelerator.
+///
+/// The memory is unmapped, and freed if it was allocated by OPAE, when the
+/// buffer is dropped.
+#[derive(Debug)]
+pub struct SharedBuffer<'handle> {
+    handle: &'handle Handle,
+    ptr: *mut u8,
+    len: usize,
+    wsid: u64,
+    io_address: u64,
+    _memory: PhantomData<&'handle mut [u8]>,
+}
+
+impl<'handle> SharedBuffer<'handle> {
+    /// Allocates a new buffer of `len` bytes, shared with the accelerator of
+    /// `handle`.
+    pub fn new(handle: &'handle Handle, len: usize) -> Result<Self> {
+        Self::prepare(handle, ptr::null_mut(), len, 0)
+    }
+
+    /// Shares the existing `memory` with the accelerator of `handle`. The
+    /// memory must be page-aligned and its length a multiple of the page size.
+    pub fn preallocated(handle: &'handle Handle, memory: &'handle mut [u8]) -> Result<Self> {
+        Self::prepare(
+            handle,
+            memory.as_mut_ptr(),
+            memory.len(),
+            fpga_buffer_flags::FPGA_BUF_PREALLOCATED as i32,
+        )
+    }
+
+    fn prepare(handle: &'handle Handle, ptr: *mut u8, len: usize, flags: i32) -> Result<Self> {
+        let mut buf_addr = ptr.cast();
+        let mut wsid = 0;
+        Result::from(unsafe {
+            fpgaPrepareBuffer(**handle, len as u64, &mut buf_addr, &mut wsid, flags)
+        })?;
+
+        // Construct the buffer first, so it is released if this fails.
+        let mut buffer = Self {
+            handle,
+            ptr: buf_addr.cast(),
+            len,
+            wsid,
+            io_address: 0,
+            _memory: PhantomData,
+        };
+        Result::from(unsafe { fpgaGetIOAddress(**handle, wsid, &mut buffer.io_address) })?;
+
+        Ok(buffer)
+    }
+
+    /// Returns the IO address of this buffer, to be used by the accelerator
+    /// to access it.
+    pub fn io_address(&self) -> u64 {
+        self.io_address
+    }
+
+    /// Returns the workspace ID of this buffer.
+    pub fn wsid(&self) -> u64 {
+        self.wsid
+    }
+}
+
+impl Deref for SharedBuffer<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // Safety:
+        // - The memory is mapped until the buffer is dropped.
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl DerefMut for SharedBuffer<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // Safety:
+        // - The memory is mapped until the buffer is dropped.
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for SharedBuffer<'_> {
+    fn drop(&mut self) {
+        trace!("Dropping SharedBuffer: {:#x}", self.wsid);
+        if Result::from(unsafe { fpgaReleaseBuffer(**self.handle, self.wsid) }).is_err() {
+            error!("Failed to release buffer {:#x}", self.wsid);
+        }
+    }
+}
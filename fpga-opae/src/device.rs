@@ -1,5 +1,6 @@
 use crate::{
-    bindings::fpga_version, DeviceProperties, Properties, ResourceInfo, ResourceProperties, Token,
+    bindings::fpga_version, DeviceProperties, Handle, OpenMode, Properties, ResourceInfo,
+    ResourceProperties, Result, Token,
 };
 use std::fmt::Debug;
 
@@ -14,6 +15,11 @@ impl Device {
         Device { token, properties }
     }
 
+    /// Opens this device, e.g. to reconfigure its slots.
+    pub fn open(&self) -> Result<Handle> {
+        Handle::open(&self.token, OpenMode::Exclusive, 0)
+    }
+
     pub fn info(&self) -> DeviceInfo {
         DeviceInfo {
             resource: self.into(),
@@ -1,19 +1,43 @@
 use crate::bindings::{fpgaErrStr, fpga_result};
+use fpga_core::MmioError;
 use std::ffi::CStr;
+use uuid::Uuid;
 
-/// Error wrapper for `bindings::fpga_result`.
-#[derive(Copy, Clone, Debug)]
-pub struct Error(fpga_result);
+/// Errors returned by the OPAE platform.
+#[derive(Clone, Debug)]
+pub enum Error {
+    /// Error wrapper for `bindings::fpga_result`.
+    Opae(fpga_result),
+    /// The accelerator must be opened with [crate::Opae::open] first.
+    NotOpened,
+    /// Invalid access to an MMIO space.
+    Mmio(MmioError),
+    /// The GBS file could not be parsed.
+    Gbs(fpga_gbs::Error),
+    /// The GBS file targets a different PR interface than the one of the
+    /// device.
+    IncompatibleGbs { device: Uuid, gbs: Uuid },
+}
 
 impl std::error::Error for Error {}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            unsafe { CStr::from_ptr(fpgaErrStr(self.0)) }.to_string_lossy()
-        )
+        match self {
+            Self::Opae(result) => write!(
+                f,
+                "{}",
+                unsafe { CStr::from_ptr(fpgaErrStr(*result)) }.to_string_lossy()
+            ),
+            Self::NotOpened => write!(f, "accelerator is not opened"),
+            Self::Mmio(error) => write!(f, "{}", error),
+            Self::Gbs(error) => write!(f, "{}", error),
+            Self::IncompatibleGbs { device, gbs } => write!(
+                f,
+                "GBS targets PR interface {}, but device has PR interface {}",
+                gbs, device
+            ),
+        }
     }
 }
 
@@ -24,13 +48,43 @@ impl From<fpga_result> for Result<()> {
     fn from(result: fpga_result) -> Self {
         match result {
             fpga_result::FPGA_OK => Ok(()),
-            _ => Err(Error(result)),
+            _ => Err(Error::Opae(result)),
         }
     }
 }
 
 impl From<fpga_result> for Error {
     fn from(fpga_result: fpga_result) -> Self {
-        Self(fpga_result)
+        Self::Opae(fpga_result)
+    }
+}
+
+impl From<MmioError> for Error {
+    fn from(error: MmioError) -> Self {
+        Self::Mmio(error)
+    }
+}
+
+impl From<fpga_gbs::Error> for Error {
+    fn from(error: fpga_gbs::Error) -> Self {
+        Self::Gbs(error)
+    }
+}
+
+impl From<Error> for fpga_core::Error {
+    fn from(error: Error) -> Self {
+        let kind: fn(fpga_core::Context) -> Self = match error {
+            Error::Opae(fpga_result::FPGA_INVALID_PARAM)
+            | Error::NotOpened
+            | Error::Mmio(_)
+            | Error::Gbs(_)
+            | Error::IncompatibleGbs { .. } => Self::InvalidParam,
+            Error::Opae(fpga_result::FPGA_BUSY) => Self::Busy,
+            Error::Opae(fpga_result::FPGA_NOT_FOUND) => Self::NotFound,
+            Error::Opae(fpga_result::FPGA_NOT_SUPPORTED) => Self::Unsupported,
+            Error::Opae(fpga_result::FPGA_NO_ACCESS) => Self::PermissionDenied,
+            Error::Opae(_) => Self::Backend,
+        };
+        kind(fpga_core::Context::new().with_source(error))
     }
 }
@@ -0,0 +1,437 @@
+use crate::{
+    bindings::{
+        fpgaCreateEventHandle, fpgaDestroyEventHandle, fpgaGetOSObjectFromEventHandle,
+        fpga_event_handle, fpga_event_type, fpga_result,
+    },
+    Error, Result,
+};
+use log::{error, trace};
+use std::{
+    convert::TryFrom,
+    future::Future,
+    ops::Deref,
+    os::unix::io::{AsRawFd, RawFd},
+    ptr,
+    time::Duration,
+};
+
+/// Type of the events signaled on an [EventHandle].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventType {
+    /// A user interrupt of the accelerator.
+    Interrupt,
+    /// An error of the resource.
+    Error,
+    /// A power or thermal event of the device.
+    PowerThermal,
+}
+
+impl From<EventType> for fpga_event_type {
+    fn from(event_type: EventType) -> Self {
+        match event_type {
+            EventType::Interrupt => fpga_event_type::FPGA_EVENT_INTERRUPT,
+            EventType::Error => fpga_event_type::FPGA_EVENT_ERROR,
+            EventType::PowerThermal => fpga_event_type::FPGA_EVENT_POWER_THERMAL,
+        }
+    }
+}
+
+/// Handle to an OS event object on which events of an accelerator are
+/// signaled, after registering it with [crate::Handle::register_event].
+#[derive(Debug)]
+pub struct EventHandle {
+    event_handle: fpga_event_handle,
+    fd: RawFd,
+    #[cfg(feature = "tokio")]
+    registration: reactor::Registration,
+}
+
+impl EventHandle {
+    pub fn new() -> Result<Self> {
+        let mut event_handle = ptr::null_mut();
+        Result::from(unsafe { fpgaCreateEventHandle(&mut event_handle) })?;
+
+        // Construct the event handle first, so it is destroyed if this fails.
+        let mut event = Self {
+            event_handle,
+            fd: -1,
+            #[cfg(feature = "tokio")]
+            registration: reactor::Registration::default(),
+        };
+        Result::from(unsafe { fpgaGetOSObjectFromEventHandle(event_handle, &mut event.fd) })?;
+
+        Ok(event)
+    }
+
+    /// Returns the number of events signaled since the last wait, or `None`
+    /// if no event was signaled. Does not block.
+    pub fn poll(&self) -> Result<Option<u64>> {
+        try_read_fd(self.fd)
+    }
+
+    /// Blocks until an event is signaled or `timeout` elapses, and returns the
+    /// number of events signaled since the last wait, or `None` on timeout.
+    /// Waits indefinitely without timeout.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<Option<u64>> {
+        let timeout_ms = timeout
+            .map(|timeout| i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX))
+            .unwrap_or(-1);
+        if poll_fd(self.fd, timeout_ms)? {
+            self.poll()
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the file descriptor of the OS event object, e.g. to register
+    /// it with epoll. It becomes readable when an event is signaled.
+    pub fn fd(&self) -> RawFd {
+        self.fd
+    }
+
+    /// Returns a future that resolves to the number of events signaled since
+    /// the last wait.
+    ///
+    /// Without the `tokio` feature the future is runtime-agnostic, and is
+    /// woken by a single helper thread shared by all pending futures. With the
+    /// `tokio` feature the event is registered once with the reactor of the
+    /// tokio runtime of the first wait, which is then used by all waits on this
+    /// event handle.
+    pub fn wait_async(&self) -> impl Future<Output = Result<u64>> + '_ {
+        #[cfg(not(feature = "tokio"))]
+        return agnostic::EventFuture::new(self.fd);
+
+        #[cfg(feature = "tokio")]
+        return self.registration.wait(self.fd);
+    }
+}
+
+impl Deref for EventHandle {
+    type Target = fpga_event_handle;
+
+    fn deref(&self) -> &fpga_event_handle {
+        &self.event_handle
+    }
+}
+
+impl AsRawFd for EventHandle {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for EventHandle {
+    fn drop(&mut self) {
+        trace!("Dropping EventHandle: {:p}", self.event_handle);
+        // Deregister the file descriptor before it is closed.
+        #[cfg(feature = "tokio")]
+        self.registration.deregister();
+        if Result::from(unsafe { fpgaDestroyEventHandle(&mut self.event_handle) }).is_err() {
+            error!("Failed to destroy event handle");
+        }
+    }
+}
+
+/// Returns true if the event object `fd` is signaled within `timeout_ms`
+/// milliseconds. A negative timeout waits indefinitely.
+fn poll_fd(fd: RawFd, timeout_ms: i32) -> Result<bool> {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    match unsafe { libc::poll(&mut pollfd, 1, timeout_ms) } {
+        -1 => Err(Error::Opae(fpga_result::FPGA_EXCEPTION)),
+        0 => Ok(false),
+        _ if pollfd.revents & libc::POLLIN != 0 => Ok(true),
+        // The file descriptor was closed or is invalid.
+        _ => Err(Error::Opae(fpga_result::FPGA_EXCEPTION)),
+    }
+}
+
+/// Reads and resets the event counter of `fd`, if it is signaled.
+fn try_read_fd(fd: RawFd) -> Result<Option<u64>> {
+    if !poll_fd(fd, 0)? {
+        return Ok(None);
+    }
+    let mut count = 0u64;
+    match unsafe { libc::read(fd, &mut count as *mut u64 as *mut libc::c_void, 8) } {
+        8 => Ok(Some(count)),
+        _ => Err(Error::Opae(fpga_result::FPGA_EXCEPTION)),
+    }
+}
+
+#[cfg(not(feature = "tokio"))]
+mod agnostic {
+    use super::try_read_fd;
+    use crate::{bindings::fpga_result, Error, Result};
+    use log::error;
+    use std::{
+        collections::HashMap,
+        future::Future,
+        io,
+        marker::PhantomData,
+        os::unix::io::RawFd,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex, Once, OnceLock,
+        },
+        task::{Context, Poll, Waker},
+        thread,
+    };
+
+    /// Maximum number of signaled event objects handled per wakeup of the
+    /// poller thread.
+    const MAX_EVENTS: usize = 16;
+
+    /// Source of the ids of event futures, used to tell apart futures that
+    /// wait on the same event object.
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    /// Waits for the event objects of all pending futures on a single helper
+    /// thread, using one epoll set.
+    #[derive(Debug)]
+    struct Poller {
+        epoll_fd: RawFd,
+        /// Wakers of the pending futures by id, per registered event object.
+        wakers: Mutex<HashMap<RawFd, Vec<(u64, Waker)>>>,
+    }
+
+    impl Poller {
+        /// Returns the poller, starting its thread on first use.
+        fn get() -> Result<&'static Self> {
+            static POLLER: OnceLock<Result<Poller>> = OnceLock::new();
+            static START: Once = Once::new();
+
+            let poller = POLLER
+                .get_or_init(Self::new)
+                .as_ref()
+                .map_err(Clone::clone)?;
+            START.call_once(|| {
+                thread::Builder::new()
+                    .name("fpga-opae-events".to_string())
+                    .spawn(move || poller.run())
+                    .expect("failed to spawn event poller thread");
+            });
+            Ok(poller)
+        }
+
+        fn new() -> Result<Self> {
+            match unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) } {
+                -1 => Err(Error::Opae(fpga_result::FPGA_EXCEPTION)),
+                epoll_fd => Ok(Self {
+                    epoll_fd,
+                    wakers: Mutex::default(),
+                }),
+            }
+        }
+
+        /// Waits for registered event objects to be signaled, and wakes the
+        /// futures waiting on them. Event objects are removed from the epoll
+        /// set once signaled, and registered again by futures that are still
+        /// pending after they are polled.
+        fn run(&self) {
+            let mut events = [libc::epoll_event { events: 0, u64: 0 }; MAX_EVENTS];
+            loop {
+                let count = unsafe {
+                    libc::epoll_wait(self.epoll_fd, events.as_mut_ptr(), MAX_EVENTS as i32, -1)
+                };
+                if count == -1 {
+                    let error = io::Error::last_os_error();
+                    if error.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    error!("Event poller failed: {}", error);
+                    return;
+                }
+
+                let mut wakers = self.wakers.lock().unwrap();
+                for event in &events[..count as usize] {
+                    let fd = event.u64 as RawFd;
+                    if let Some(pending) = wakers.remove(&fd) {
+                        self.ctl(libc::EPOLL_CTL_DEL, fd).ok();
+                        pending.into_iter().for_each(|(_, waker)| waker.wake());
+                    }
+                }
+            }
+        }
+
+        fn ctl(&self, op: libc::c_int, fd: RawFd) -> Result<()> {
+            let mut event = libc::epoll_event {
+                events: libc::EPOLLIN as u32,
+                u64: fd as u64,
+            };
+            match unsafe { libc::epoll_ctl(self.epoll_fd, op, fd, &mut event) } {
+                -1 => Err(Error::Opae(fpga_result::FPGA_EXCEPTION)),
+                _ => Ok(()),
+            }
+        }
+
+        /// Registers `waker` of future `id` to be woken when event object `fd`
+        /// is signaled.
+        fn register(&self, fd: RawFd, id: u64, waker: &Waker) -> Result<()> {
+            let mut wakers = self.wakers.lock().unwrap();
+            match wakers.get_mut(&fd) {
+                Some(pending) => match pending.iter_mut().find(|(other, _)| *other == id) {
+                    Some((_, registered)) => *registered = waker.clone(),
+                    None => pending.push((id, waker.clone())),
+                },
+                None => {
+                    self.ctl(libc::EPOLL_CTL_ADD, fd)?;
+                    wakers.insert(fd, vec![(id, waker.clone())]);
+                }
+            }
+            Ok(())
+        }
+
+        /// Removes the waker of future `id` waiting on event object `fd`.
+        fn unregister(&self, fd: RawFd, id: u64) {
+            let mut wakers = self.wakers.lock().unwrap();
+            if let Some(pending) = wakers.get_mut(&fd) {
+                pending.retain(|(other, _)| *other != id);
+                if pending.is_empty() {
+                    wakers.remove(&fd);
+                    self.ctl(libc::EPOLL_CTL_DEL, fd).ok();
+                }
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub(super) struct EventFuture<'event> {
+        fd: RawFd,
+        id: u64,
+        registered: bool,
+        _event: PhantomData<&'event ()>,
+    }
+
+    impl EventFuture<'_> {
+        pub(super) fn new(fd: RawFd) -> Self {
+            Self {
+                fd,
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                registered: false,
+                _event: PhantomData,
+            }
+        }
+    }
+
+    impl Future for EventFuture<'_> {
+        type Output = Result<u64>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(count) = try_read_fd(self.fd).transpose() {
+                return Poll::Ready(count);
+            }
+
+            // The epoll set is level-triggered, so an event signaled after the
+            // read above still wakes this future.
+            Poller::get()?.register(self.fd, self.id, cx.waker())?;
+            self.registered = true;
+            Poll::Pending
+        }
+    }
+
+    impl Drop for EventFuture<'_> {
+        fn drop(&mut self) {
+            if self.registered {
+                if let Ok(poller) = Poller::get() {
+                    poller.unregister(self.fd, self.id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod reactor {
+    use super::try_read_fd;
+    use crate::{bindings::fpga_result, Error, Result};
+    use std::{
+        cell::OnceCell,
+        os::unix::io::{AsRawFd, RawFd},
+    };
+    use tokio::io::{unix::AsyncFd, Interest};
+
+    /// Borrowed file descriptor of an event handle.
+    #[derive(Debug)]
+    struct Fd(RawFd);
+
+    impl AsRawFd for Fd {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0
+        }
+    }
+
+    fn exception() -> Error {
+        Error::Opae(fpga_result::FPGA_EXCEPTION)
+    }
+
+    /// Registration of the file descriptor of an event handle with the tokio
+    /// reactor. A file descriptor can only be registered once, so all waits on
+    /// the event handle share it.
+    #[derive(Debug, Default)]
+    pub(super) struct Registration(OnceCell<AsyncFd<Fd>>);
+
+    impl Registration {
+        fn async_fd(&self, fd: RawFd) -> Result<&AsyncFd<Fd>> {
+            if let Some(async_fd) = self.0.get() {
+                return Ok(async_fd);
+            }
+            // Safety:
+            // - The event handle, which owns the file descriptor, deregisters
+            //   it before it is closed.
+            let async_fd = unsafe { AsyncFd::register_with_interest(Fd(fd), Interest::READABLE) }
+                .map_err(|_| exception())?;
+            Ok(self.0.get_or_init(|| async_fd))
+        }
+
+        pub(super) async fn wait(&self, fd: RawFd) -> Result<u64> {
+            let async_fd = self.async_fd(fd)?;
+            loop {
+                let mut guard = async_fd.readable().await.map_err(|_| exception())?;
+                match try_read_fd(fd)? {
+                    Some(count) => return Ok(count),
+                    None => guard.clear_ready(),
+                }
+            }
+        }
+
+        pub(super) fn deregister(&mut self) {
+            self.0.take();
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use std::time::Duration;
+
+        #[tokio::test]
+        async fn concurrent_waits() {
+            let fd = unsafe { libc::eventfd(0, 0) };
+            assert!(fd >= 0);
+            let signal = |count: u64| {
+                let written =
+                    unsafe { libc::write(fd, &count as *const u64 as *const libc::c_void, 8) };
+                assert_eq!(written, 8);
+            };
+
+            let mut registration = Registration::default();
+            let (first, second, ()) =
+                tokio::join!(registration.wait(fd), registration.wait(fd), async {
+                    tokio::task::yield_now().await;
+                    signal(1);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    signal(2);
+                });
+            let mut counts = [first.unwrap(), second.unwrap()];
+            counts.sort_unstable();
+            assert_eq!(counts, [1, 2]);
+
+            registration.deregister();
+            unsafe { libc::close(fd) };
+        }
+    }
+}
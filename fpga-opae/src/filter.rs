@@ -1,6 +1,8 @@
 use crate::{
-    bindings::{fpgaEnumerate, fpga_accelerator_state, fpga_objtype, fpga_token, fpga_version},
-    Properties, Resource, Result, Token,
+    bindings::{
+        fpgaEnumerate, fpga_accelerator_state, fpga_objtype, fpga_result, fpga_token, fpga_version,
+    },
+    Error, Properties, Resource, Result, Token,
 };
 use std::{ffi::CString, fmt::Debug, iter::FilterMap, vec};
 use uuid::Uuid;
@@ -148,6 +150,38 @@ impl Filter {
         self
     }
 
+    /// Selects the resource at the PCIe address formatted as
+    /// `segment:bus:device.function`, or `bus:device.function` in segment 0.
+    /// Returns an error when the address can't be parsed.
+    pub fn with_pci_address<T>(self, address: T) -> Result<Self>
+    where
+        T: AsRef<str>,
+    {
+        let invalid = || Error::from(fpga_result::FPGA_INVALID_PARAM);
+        let parse = |part: &str, max: u32| {
+            u32::from_str_radix(part, 16)
+                .ok()
+                .filter(|&nr| nr <= max)
+                .ok_or_else(invalid)
+        };
+        let (address, function) = address.as_ref().rsplit_once('.').ok_or_else(invalid)?;
+        let parts = address.split(':').collect::<Vec<_>>();
+        let (segment, bus, device) = match parts.as_slice() {
+            [segment, bus, device] => (*segment, *bus, *device),
+            [bus, device] => ("0", *bus, *device),
+            _ => return Err(invalid()),
+        };
+        let pci_segment_nr = parse(segment, 0xffff)? as u16;
+        let pci_bus_nr = parse(bus, 0xff)? as u8;
+        let pci_device_nr = parse(device, 0x1f)? as u8;
+        let pci_function_nr = parse(function, 0x7)? as u8;
+        Ok(self
+            .with_pci_segment_nr(pci_segment_nr)
+            .with_pci_bus_nr(pci_bus_nr)
+            .with_pci_device_nr(pci_device_nr)
+            .with_pci_function_nr(pci_function_nr))
+    }
+
     pub fn with_socket_id(mut self, socket_id: u8) -> Self {
         self.socket_id.replace(socket_id);
         self
@@ -0,0 +1,166 @@
+use crate::{
+    bindings::{
+        fpgaAssignToInterface, fpgaClose, fpgaMapMMIO, fpgaOpen, fpgaReadMMIO32, fpgaReadMMIO64,
+        fpgaReconfigureSlot, fpgaRegisterEvent, fpgaReleaseFromInterface, fpgaUnmapMMIO,
+        fpgaUnregisterEvent, fpgaWriteMMIO32, fpgaWriteMMIO64, fpga_handle, fpga_open_flags,
+        fpga_reconf_flags,
+    },
+    Accelerator, EventHandle, EventType, Result, Token,
+};
+use log::{error, trace};
+use std::{ops::Deref, ptr};
+
+/// Access mode used when opening an accelerator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpenMode {
+    /// Only this handle can access the accelerator.
+    Exclusive,
+    /// Other processes can open the accelerator in shared mode as well.
+    Shared,
+}
+
+/// Handle to an opened accelerator, with all its MMIO spaces mapped.
+///
+/// The MMIO spaces are unmapped and the accelerator is closed when the handle
+/// is dropped.
+#[derive(Debug)]
+pub struct Handle {
+    handle: fpga_handle,
+    num_mmio_spaces: u32,
+}
+
+impl Handle {
+    /// Opens the accelerator of `token` and maps its `num_mmio_spaces` MMIO
+    /// spaces.
+    pub fn open(token: &Token, mode: OpenMode, num_mmio_spaces: u32) -> Result<Self> {
+        let flags = match mode {
+            OpenMode::Exclusive => 0,
+            OpenMode::Shared => fpga_open_flags::FPGA_OPEN_SHARED as i32,
+        };
+
+        let mut handle = ptr::null_mut();
+        Result::from(unsafe { fpgaOpen(**token, &mut handle, flags) })?;
+
+        // Construct the handle first, so it is closed if mapping fails.
+        let mut handle = Self {
+            handle,
+            num_mmio_spaces: 0,
+        };
+        for mmio_num in 0..num_mmio_spaces {
+            Result::from(unsafe { fpgaMapMMIO(handle.handle, mmio_num, ptr::null_mut()) })?;
+            handle.num_mmio_spaces += 1;
+        }
+
+        Ok(handle)
+    }
+
+    /// Returns the number of mapped MMIO spaces.
+    pub fn num_mmio_spaces(&self) -> u32 {
+        self.num_mmio_spaces
+    }
+
+    /// Reads the 32-bit value at byte `offset` of MMIO space `mmio_num`.
+    pub fn read_mmio32(&self, mmio_num: u32, offset: u64) -> Result<u32> {
+        let mut value = Default::default();
+        Result::from(unsafe { fpgaReadMMIO32(self.handle, mmio_num, offset, &mut value) })
+            .map(|_| value)
+    }
+
+    /// Reads the 64-bit value at byte `offset` of MMIO space `mmio_num`.
+    pub fn read_mmio64(&self, mmio_num: u32, offset: u64) -> Result<u64> {
+        let mut value = Default::default();
+        Result::from(unsafe { fpgaReadMMIO64(self.handle, mmio_num, offset, &mut value) })
+            .map(|_| value)
+    }
+
+    /// Writes the 32-bit `value` at byte `offset` of MMIO space `mmio_num`.
+    ///
+    /// OPAE serializes accesses to a handle, so this does not require a
+    /// mutable handle. This allows writing e.g. the IO address of a
+    /// [crate::SharedBuffer] that borrows the handle.
+    pub fn write_mmio32(&self, mmio_num: u32, offset: u64, value: u32) -> Result<()> {
+        Result::from(unsafe { fpgaWriteMMIO32(self.handle, mmio_num, offset, value) })
+    }
+
+    /// Writes the 64-bit `value` at byte `offset` of MMIO space `mmio_num`.
+    pub fn write_mmio64(&self, mmio_num: u32, offset: u64, value: u64) -> Result<()> {
+        Result::from(unsafe { fpgaWriteMMIO64(self.handle, mmio_num, offset, value) })
+    }
+
+    /// Registers `event` to be signaled on events of `event_type`. For
+    /// [EventType::Interrupt], `flags` is the user interrupt vector.
+    pub fn register_event(
+        &self,
+        event: &EventHandle,
+        event_type: EventType,
+        flags: u32,
+    ) -> Result<()> {
+        Result::from(unsafe { fpgaRegisterEvent(self.handle, event_type.into(), **event, flags) })
+    }
+
+    /// Unregisters `event` from events of `event_type`.
+    pub fn unregister_event(&self, event: &EventHandle, event_type: EventType) -> Result<()> {
+        Result::from(unsafe { fpgaUnregisterEvent(self.handle, event_type.into(), **event) })
+    }
+
+    /// Reconfigures slot `slot` of this device with the GBS file in
+    /// `bitstream`. With `force` the slot is reconfigured even if the
+    /// accelerator is in use.
+    pub fn reconfigure_slot(&self, slot: u32, bitstream: &[u8], force: bool) -> Result<()> {
+        let flags = if force {
+            fpga_reconf_flags::FPGA_RECONF_FORCE as i32
+        } else {
+            0
+        };
+        Result::from(unsafe {
+            fpgaReconfigureSlot(
+                self.handle,
+                slot,
+                bitstream.as_ptr(),
+                bitstream.len() as _,
+                flags,
+            )
+        })
+    }
+
+    /// Assigns the port of `accelerator` to host interface `interface` of
+    /// this device.
+    pub fn assign_to_interface(&self, accelerator: &Accelerator, interface: u32) -> Result<()> {
+        Result::from(unsafe {
+            fpgaAssignToInterface(self.handle, **accelerator.token(), interface, 0)
+        })
+    }
+
+    /// Releases the port of `accelerator` from its host interface.
+    pub fn release_from_interface(&self, accelerator: &Accelerator) -> Result<()> {
+        Result::from(unsafe { fpgaReleaseFromInterface(self.handle, **accelerator.token()) })
+    }
+
+    /// Registers `event` to be signaled on user interrupt `vector` of the
+    /// accelerator.
+    pub fn register_interrupt(&self, event: &EventHandle, vector: u32) -> Result<()> {
+        self.register_event(event, EventType::Interrupt, vector)
+    }
+}
+
+impl Deref for Handle {
+    type Target = fpga_handle;
+
+    fn deref(&self) -> &fpga_handle {
+        &self.handle
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        trace!("Dropping Handle: {:p}", self.handle);
+        for mmio_num in 0..self.num_mmio_spaces {
+            if Result::from(unsafe { fpgaUnmapMMIO(self.handle, mmio_num) }).is_err() {
+                error!("Failed to unmap MMIO space {}", mmio_num);
+            }
+        }
+        if Result::from(unsafe { fpgaClose(self.handle) }).is_err() {
+            error!("Failed to close handle");
+        }
+    }
+}
@@ -1,5 +1,8 @@
-use fpga_core::Platform;
+use fpga_core::{DynMMIO, DynProgram, Platform, PlatformType, Program, MMIO};
+use fpga_gbs::Gbs;
+use log::{error, info};
 use std::fmt::Debug;
+use uuid::Uuid;
 
 pub mod bindings;
 use bindings::{fpga_objtype, fpga_result};
@@ -13,9 +16,21 @@ pub use resource::*;
 mod accelerator;
 pub use accelerator::*;
 
+mod buffer;
+pub use buffer::*;
+
+mod event;
+pub use event::*;
+
 mod filter;
 pub use filter::*;
 
+mod handle;
+pub use handle::*;
+
+mod program;
+pub use program::*;
+
 mod properties;
 pub use properties::*;
 
@@ -28,6 +43,7 @@ pub use errors::*;
 pub struct Opae {
     device: Option<Device>,
     accelerator: Accelerator,
+    handle: Option<Handle>,
 }
 
 impl Opae {
@@ -51,6 +67,7 @@ impl Opae {
                     Resource::Accelerator(accelerator) => Self {
                         device: accelerator.device(),
                         accelerator,
+                        handle: None,
                     },
                     // Safety:
                     // - Filter is selecting accelerators as checked above.
@@ -63,6 +80,55 @@ impl Opae {
         }
     }
 
+    /// Returns all accelerators on this system, in order of enumeration.
+    pub fn enumerate() -> Vec<Self> {
+        Filter::new()
+            .with_accelerator_object()
+            .into_iter()
+            .filter_map(|resource| match resource {
+                Resource::Accelerator(accelerator) => Some(Self {
+                    device: accelerator.device(),
+                    accelerator,
+                    handle: None,
+                }),
+                Resource::Device(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn accelerator(&self) -> &Accelerator {
+        &self.accelerator
+    }
+
+    pub fn device(&self) -> Option<&Device> {
+        self.device.as_ref()
+    }
+
+    /// Opens the accelerator of this platform, which is required for MMIO.
+    /// Replaces the current handle, if any.
+    pub fn open(&mut self, mode: OpenMode) -> Result<&mut Handle> {
+        // Close the current handle first, it may hold exclusive access.
+        self.handle.take();
+        Ok(self.handle.insert(self.accelerator.open(mode)?))
+    }
+
+    /// Closes the accelerator of this platform.
+    pub fn close(&mut self) {
+        self.handle.take();
+    }
+
+    pub fn handle(&self) -> Option<&Handle> {
+        self.handle.as_ref()
+    }
+
+    pub fn handle_mut(&mut self) -> Option<&mut Handle> {
+        self.handle.as_mut()
+    }
+
+    fn opened(&self) -> Result<&Handle> {
+        self.handle.as_ref().ok_or(Error::NotOpened)
+    }
+
     pub fn accelerator_info(&self) -> AcceleratorInfo {
         self.accelerator.info()
     }
@@ -87,14 +153,128 @@ impl Debug for Opae {
 }
 
 impl Platform for Opae {
-    const NAME: &'static str = "OPAE";
-
     type Configuration = Filter;
     type Error = Error;
 
+    fn platform(&self) -> PlatformType {
+        PlatformType::OPAE
+    }
+
     fn from_configuration(configuration: Self::Configuration) -> Result<Self> {
         Self::from_filter(configuration)
     }
+
+    /// Returns `None` until the accelerator is opened with [Opae::open].
+    fn dyn_mmio(&self) -> Option<&dyn DynMMIO> {
+        self.handle.as_ref().map(|_| self as &dyn DynMMIO)
+    }
+
+    /// Returns `None` until the accelerator is opened with [Opae::open].
+    fn dyn_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        match self.handle {
+            Some(_) => Some(self),
+            None => None,
+        }
+    }
+
+    fn dyn_program(&mut self) -> Option<&mut dyn DynProgram> {
+        Some(self)
+    }
+}
+
+/// Partial reconfiguration of the device of the accelerator. Closes the
+/// accelerator, which must be opened again with [Opae::open] afterwards.
+impl Program for Opae {
+    type Source = Reconfiguration;
+    /// The GUID of the new accelerator.
+    type Output = Uuid;
+
+    fn program(&mut self, source: Self::Source) -> Result<Self::Output> {
+        let device = self
+            .device
+            .as_ref()
+            .ok_or(Error::Opae(fpga_result::FPGA_NOT_FOUND))?;
+
+        // The GUID of a device is the UUID of its PR interface.
+        let gbs = Gbs::parse(&source.gbs)?;
+        let interface_uuid = device.guid()?;
+        if gbs.interface_uuid() != interface_uuid {
+            return Err(Error::IncompatibleGbs {
+                device: interface_uuid,
+                gbs: gbs.interface_uuid(),
+            });
+        }
+
+        let device = device.open()?;
+        // The accelerator can't be reconfigured while it is opened.
+        self.close();
+
+        if source.port_interface.is_some() {
+            device.release_from_interface(&self.accelerator)?;
+        }
+        info!("Reconfiguring slot {}", source.slot);
+        let result = device.reconfigure_slot(source.slot, &source.gbs, source.force);
+        // Assign the port back, also if reconfiguration failed, in which case
+        // the reconfiguration error is returned.
+        if let Some(interface) = source.port_interface {
+            if let Err(assign_error) = device.assign_to_interface(&self.accelerator, interface) {
+                match result {
+                    Ok(_) => return Err(assign_error),
+                    Err(_) => error!("Failed to assign port back: {}", assign_error),
+                }
+            }
+        }
+        result?;
+
+        // Enumerate the accelerator again, as its properties changed.
+        let object_id = self.accelerator.object_id()?;
+        self.accelerator = Filter::new()
+            .with_accelerator_object()
+            .with_object_id(object_id)
+            .into_iter()
+            .next()
+            .and_then(|resource| match resource {
+                Resource::Accelerator(accelerator) => Some(accelerator),
+                Resource::Device(_) => None,
+            })
+            .ok_or(Error::Opae(fpga_result::FPGA_NOT_FOUND))?;
+        self.accelerator.guid()
+    }
+}
+
+/// MMIO on the MMIO spaces of the accelerator. Requires the accelerator to be
+/// opened with [Opae::open].
+impl MMIO for Opae {
+    fn num_spaces(&self) -> u32 {
+        self.handle
+            .as_ref()
+            .map(Handle::num_mmio_spaces)
+            .unwrap_or_default()
+    }
+
+    fn read_u32(&self, space: u32, offset: usize) -> Result<u32> {
+        let handle = self.opened()?;
+        self.check_access(space, offset, 4, 4)?;
+        handle.read_mmio32(space, offset as u64)
+    }
+
+    fn read_u64(&self, space: u32, offset: usize) -> Result<u64> {
+        let handle = self.opened()?;
+        self.check_access(space, offset, 8, 8)?;
+        handle.read_mmio64(space, offset as u64)
+    }
+
+    fn write_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<()> {
+        let handle = self.opened()?;
+        self.check_access(space, offset, 4, 4)?;
+        handle.write_mmio32(space, offset as u64, value)
+    }
+
+    fn write_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<()> {
+        let handle = self.opened()?;
+        self.check_access(space, offset, 8, 8)?;
+        handle.write_mmio64(space, offset as u64, value)
+    }
 }
 
 #[cfg(test)]
@@ -105,4 +285,30 @@ mod tests {
     fn bad_filter() {
         assert!(Opae::from_filter(Filter::new().with_device_object()).is_err());
     }
+
+    #[test]
+    fn pci_address() {
+        let filter = Filter::new().with_pci_address("0001:af:00.1").unwrap();
+        assert_eq!(filter.pci_segment_nr, Some(1));
+        assert_eq!(filter.pci_bus_nr, Some(0xaf));
+        assert_eq!(filter.pci_device_nr, Some(0));
+        assert_eq!(filter.pci_function_nr, Some(1));
+        assert_eq!(
+            Filter::new()
+                .with_pci_address("af:00.1")
+                .unwrap()
+                .pci_segment_nr,
+            Some(0)
+        );
+        assert!(Filter::new().with_pci_address("af:00.8").is_err());
+        assert!(Filter::new().with_pci_address("0:0001:af:00.1").is_err());
+        assert!(Filter::new().with_pci_address("3b.00:0").is_err());
+        assert!(Filter::new().with_pci_address("0000.3b.00.0").is_err());
+        assert!(Filter::new().with_pci_address("0000:3b:00:0").is_err());
+        assert!(Filter::new().with_pci_address("3b:00.1.0").is_err());
+        assert!(Filter::new().with_pci_address("3b.00").is_err());
+        assert!(Filter::new().with_pci_address("af:20.1").is_err());
+        assert!(Filter::new().with_pci_address("10000:af:00.1").is_err());
+        assert!(Filter::new().with_pci_address("").is_err());
+    }
 }
@@ -0,0 +1,43 @@
+/// A green bitstream (GBS) to reconfigure a slot of a device with, using
+/// [fpga_core::Program] on [crate::Opae].
+#[derive(Clone, Debug)]
+pub struct Reconfiguration {
+    pub(crate) gbs: Vec<u8>,
+    pub(crate) slot: u32,
+    pub(crate) force: bool,
+    pub(crate) port_interface: Option<u32>,
+}
+
+impl Reconfiguration {
+    /// Returns a reconfiguration of slot 0 with the GBS file in `gbs`. The
+    /// GBS is validated against the PR interface of the device before
+    /// reconfiguring.
+    pub fn new(gbs: Vec<u8>) -> Self {
+        Self {
+            gbs,
+            slot: 0,
+            force: false,
+            port_interface: None,
+        }
+    }
+
+    /// Reconfigures slot `slot` of the device.
+    pub fn with_slot(mut self, slot: u32) -> Self {
+        self.slot = slot;
+        self
+    }
+
+    /// Reconfigures the slot even if the accelerator is in use.
+    pub fn with_force(mut self) -> Self {
+        self.force = true;
+        self
+    }
+
+    /// Releases the port of the accelerator from host interface `interface`,
+    /// e.g. a virtual function, before reconfiguring, and assigns it back
+    /// afterwards.
+    pub fn with_port_reassignment(mut self, interface: u32) -> Self {
+        self.port_interface = Some(interface);
+        self
+    }
+}
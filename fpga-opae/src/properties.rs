@@ -201,6 +201,18 @@ pub trait ResourceProperties {
             .map(|_| pci_segment_nr)
     }
 
+    /// Returns the PCIe address of this resource, formatted as
+    /// `segment:bus:device.function`.
+    fn pci_address(&self) -> Result<String> {
+        Ok(format!(
+            "{:04x}:{:02x}:{:02x}.{:x}",
+            self.pci_segment_nr()?,
+            self.pci_bus_nr()?,
+            self.pci_device_nr()?,
+            self.pci_function_nr()?
+        ))
+    }
+
     fn socket_id(&self) -> Result<u8> {
         let mut socket_id = Default::default();
         Result::from(unsafe { fpgaPropertiesGetSocketID(**self.properties(), &mut socket_id) })
@@ -1,5 +1,5 @@
 use fpga::xrt::Xrt;
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyRuntimeError, prelude::*};
 
 #[pyclass(unsendable)]
 pub struct PyXrt {
@@ -9,7 +9,8 @@ pub struct PyXrt {
 #[pyfunction]
 fn xrt(device_index: usize) -> PyResult<PyXrt> {
     Ok(PyXrt {
-        xrt: Xrt::from_device_index(device_index)?,
+        xrt: Xrt::from_device_index(device_index)
+            .map_err(|error| PyRuntimeError::new_err(error.to_string()))?,
     })
 }
 
@@ -0,0 +1,277 @@
+//! Derive macro for `fpga_regs::RegisterBlock`. See the `fpga-regs` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Error, Fields,
+    GenericArgument, Lit, Meta, NestedMeta, PathArguments, Result, Type,
+};
+
+/// Kind of a member of a register block.
+enum Kind {
+    Register,
+    Field { shift: u32, len: u32 },
+}
+
+/// A parsed member of a register block.
+struct Member {
+    ident: syn::Ident,
+    offset: usize,
+    /// Width of the register in bytes.
+    width: usize,
+    kind: Kind,
+    span: Span,
+}
+
+#[proc_macro_derive(RegisterBlock, attributes(register, field))]
+pub fn derive_register_block(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "RegisterBlock requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "RegisterBlock can only be derived for structs",
+            ))
+        }
+    };
+
+    let members = fields
+        .iter()
+        .map(|field| {
+            let (name, width) = register_type(&field.ty)?;
+            let ident = field.ident.clone().unwrap();
+            let attr = single_attr(&field.attrs, field.span())?;
+            let is_register = attr.path.is_ident("register");
+            if is_register != (name == "Reg") {
+                return Err(Error::new(
+                    attr.span(),
+                    "use #[register(..)] for `Reg` and #[field(..)] for `Field` members",
+                ));
+            }
+            let args = Args::parse(attr)?;
+            let offset = args
+                .offset
+                .ok_or_else(|| Error::new(attr.span(), "missing `offset` argument"))?;
+            if offset % width != 0 {
+                return Err(Error::new(
+                    attr.span(),
+                    format!("offset {:#x} is not aligned to {} bytes", offset, width),
+                ));
+            }
+            let kind = if is_register {
+                if args.bits.is_some() {
+                    return Err(Error::new(
+                        attr.span(),
+                        "bits can only be specified for fields",
+                    ));
+                }
+                Kind::Register
+            } else {
+                let (shift, len) = args
+                    .bits
+                    .ok_or_else(|| Error::new(attr.span(), "missing `bit` or `bits` argument"))?;
+                if len == 0 || shift + len > width as u32 * 8 {
+                    return Err(Error::new(
+                        attr.span(),
+                        format!("bits are out of range of a {}-bit register", width * 8),
+                    ));
+                }
+                Kind::Field { shift, len }
+            };
+            Ok(Member {
+                ident,
+                offset,
+                width,
+                kind,
+                span: field.span(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Registers may not overlap. Fields are views on registers, so they are
+    // allowed to overlap with registers and with each other.
+    let registers = members
+        .iter()
+        .filter(|member| matches!(member.kind, Kind::Register))
+        .collect::<Vec<_>>();
+    for (index, a) in registers.iter().enumerate() {
+        if let Some(b) = registers[..index]
+            .iter()
+            .find(|b| a.offset < b.offset + b.width && b.offset < a.offset + a.width)
+        {
+            return Err(Error::new(
+                a.span,
+                format!(
+                    "register `{}` overlaps with register `{}`",
+                    a.ident, b.ident
+                ),
+            ));
+        }
+    }
+
+    let size = members
+        .iter()
+        .map(|member| member.offset + member.width)
+        .max()
+        .unwrap_or_default();
+
+    let initializers = members.iter().map(|member| {
+        let ident = &member.ident;
+        let offset = member.offset;
+        match member.kind {
+            Kind::Register => quote! {
+                #ident: ::fpga_regs::Reg::new(space, base + #offset)
+            },
+            Kind::Field { shift, len } => quote! {
+                #ident: ::fpga_regs::Field::new(space, base + #offset, #shift, #len)
+            },
+        }
+    });
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the register map located at `base` in address space `space`.
+            #vis const fn new(space: u32, base: usize) -> Self {
+                Self {
+                    #(#initializers,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::fpga_regs::RegisterBlock for #ident #ty_generics #where_clause {
+            const SIZE: usize = #size;
+
+            fn at(space: u32, base: usize) -> Self {
+                Self::new(space, base)
+            }
+        }
+    })
+}
+
+/// Returns the name (`Reg` or `Field`) and width in bytes of a member type.
+fn register_type(ty: &Type) -> Result<(String, usize)> {
+    let error = || {
+        Error::new(
+            ty.span(),
+            "expected `Reg<W, A>` or `Field<W, A>` with width `W` either `u32` or `u64`",
+        )
+    };
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last().ok_or_else(error)?,
+        _ => return Err(error()),
+    };
+    let name = segment.ident.to_string();
+    if name != "Reg" && name != "Field" {
+        return Err(error());
+    }
+    let width = match segment.arguments {
+        PathArguments::AngleBracketed(ref args) => match args.args.first() {
+            Some(GenericArgument::Type(Type::Path(width))) if width.path.is_ident("u32") => 4,
+            Some(GenericArgument::Type(Type::Path(width))) if width.path.is_ident("u64") => 8,
+            _ => return Err(error()),
+        },
+        _ => return Err(error()),
+    };
+    Ok((name, width))
+}
+
+/// Returns the single `register` or `field` attribute of a member.
+fn single_attr(attrs: &[Attribute], span: Span) -> Result<&Attribute> {
+    let mut attrs = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("register") || attr.path.is_ident("field"));
+    match (attrs.next(), attrs.next()) {
+        (Some(attr), None) => Ok(attr),
+        (Some(_), Some(attr)) => Err(Error::new(
+            attr.span(),
+            "expected a single #[register(..)] or #[field(..)] attribute",
+        )),
+        _ => Err(Error::new(
+            span,
+            "missing #[register(..)] or #[field(..)] attribute",
+        )),
+    }
+}
+
+/// Arguments of a `register` or `field` attribute.
+#[derive(Default)]
+struct Args {
+    offset: Option<usize>,
+    /// Shift and length of a field.
+    bits: Option<(u32, u32)>,
+}
+
+impl Args {
+    fn parse(attr: &Attribute) -> Result<Self> {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => return Err(Error::new(meta.span(), "expected a list of arguments")),
+        };
+        let mut args = Self::default();
+        for nested in list.nested {
+            let name_value = match nested {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+                nested => return Err(Error::new(nested.span(), "expected `name = value`")),
+            };
+            let span = name_value.span();
+            match (
+                name_value
+                    .path
+                    .get_ident()
+                    .map(ToString::to_string)
+                    .as_deref(),
+                name_value.lit,
+            ) {
+                (Some("offset"), Lit::Int(offset)) => {
+                    args.offset.replace(offset.base10_parse()?);
+                }
+                (Some("bit"), Lit::Int(bit)) if args.bits.is_none() => {
+                    args.bits.replace((bit.base10_parse()?, 1));
+                }
+                (Some("bits"), Lit::Str(bits)) if args.bits.is_none() => {
+                    let error = || Error::new(bits.span(), "expected a bit range `start..end`");
+                    let value = bits.value();
+                    let mut range = value.splitn(2, "..");
+                    let mut next = || {
+                        range
+                            .next()
+                            .and_then(|bound| bound.trim().parse::<u32>().ok())
+                            .ok_or_else(error)
+                    };
+                    let (start, end) = (next()?, next()?);
+                    if end <= start {
+                        return Err(error());
+                    }
+                    args.bits.replace((start, end - start));
+                }
+                _ => {
+                    return Err(Error::new(
+                        span,
+                        "expected `offset = <int>`, `bit = <int>` or `bits = \"<start>..<end>\"`",
+                    ))
+                }
+            }
+        }
+        Ok(args)
+    }
+}
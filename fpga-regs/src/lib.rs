@@ -0,0 +1,471 @@
+//! Typed register maps on top of [MMIO].
+//!
+//! A register map is declared as a struct of [Reg] and [Field] members,
+//! annotated with their offsets, and deriving [RegisterBlock]:
+//!
+//! ```ignore
+//! use fpga_regs::{Field, Reg, RegisterBlock, RO, RW, W1C};
+//!
+//! #[derive(RegisterBlock)]
+//! struct Control {
+//!     #[register(offset = 0x00)]
+//!     ctrl: Reg<u32, RW>,
+//!     #[field(offset = 0x00, bit = 0)]
+//!     start: Field<u32, RW>,
+//!     #[field(offset = 0x00, bit = 1)]
+//!     done: Field<u32, RO>,
+//!     #[register(offset = 0x0c)]
+//!     isr: Reg<u32, W1C>,
+//!     #[field(offset = 0x10, bits = "8..16")]
+//!     version: Field<u32, RO>,
+//! }
+//!
+//! let control = Control::new(0, 0x1000);
+//! control.start.write(&mut mmio, 1)?;
+//! while !control.done.is_set(&mmio)? {}
+//! ```
+//!
+//! The width and access mode of a register are part of its type, so invalid
+//! accesses, e.g. writing a read-only register, don't compile. The derive
+//! checks the alignment of offsets, the bit ranges of fields and overlapping
+//! registers at compile time.
+
+use fpga_core::MMIO;
+use std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+};
+
+pub use fpga_regs_derive::RegisterBlock;
+
+// Allows the derive macro to be used in the tests of this crate.
+extern crate self as fpga_regs;
+
+/// A register map, implemented by `#[derive(RegisterBlock)]`.
+pub trait RegisterBlock: Sized {
+    /// Size of the register map in bytes.
+    const SIZE: usize;
+
+    /// Returns the register map located at `base` in address space `space`.
+    fn at(space: u32, base: usize) -> Self;
+}
+
+/// Width of a register.
+pub trait Width: Copy + Debug + PartialEq {
+    /// Number of bits in a register of this width.
+    const BITS: u32;
+
+    fn from_u64(value: u64) -> Self;
+    fn to_u64(self) -> u64;
+
+    fn read<M>(mmio: &M, space: u32, offset: usize) -> Result<Self, M::Error>
+    where
+        M: MMIO;
+
+    fn write<M>(mmio: &mut M, space: u32, offset: usize, value: Self) -> Result<(), M::Error>
+    where
+        M: MMIO;
+}
+
+impl Width for u32 {
+    const BITS: u32 = 32;
+
+    fn from_u64(value: u64) -> Self {
+        value as u32
+    }
+
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+
+    fn read<M>(mmio: &M, space: u32, offset: usize) -> Result<Self, M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.read_u32(space, offset)
+    }
+
+    fn write<M>(mmio: &mut M, space: u32, offset: usize, value: Self) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.write_u32(space, offset, value)
+    }
+}
+
+impl Width for u64 {
+    const BITS: u32 = 64;
+
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+
+    fn to_u64(self) -> u64 {
+        self
+    }
+
+    fn read<M>(mmio: &M, space: u32, offset: usize) -> Result<Self, M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.read_u64(space, offset)
+    }
+
+    fn write<M>(mmio: &mut M, space: u32, offset: usize, value: Self) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        mmio.write_u64(space, offset, value)
+    }
+}
+
+/// Read-only access.
+#[derive(Clone, Copy, Debug)]
+pub struct RO;
+
+/// Write-only access.
+#[derive(Clone, Copy, Debug)]
+pub struct WO;
+
+/// Read-write access.
+#[derive(Clone, Copy, Debug)]
+pub struct RW;
+
+/// Write-1-to-clear access: reads return the current bits, writing a 1 to a
+/// bit clears it and writing a 0 has no effect.
+#[derive(Clone, Copy, Debug)]
+pub struct W1C;
+
+/// Access modes that allow reads.
+pub trait Readable {}
+impl Readable for RO {}
+impl Readable for RW {}
+impl Readable for W1C {}
+
+/// Access modes that allow writes of arbitrary values.
+pub trait Writable {}
+impl Writable for WO {}
+impl Writable for RW {}
+
+/// A register of width `W` with access mode `A`.
+pub struct Reg<W, A> {
+    space: u32,
+    offset: usize,
+    _marker: PhantomData<(W, A)>,
+}
+
+impl<W, A> Reg<W, A> {
+    /// Returns the register at `offset` in address space `space`.
+    pub const fn new(space: u32, offset: usize) -> Self {
+        Self {
+            space,
+            offset,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn space(&self) -> u32 {
+        self.space
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<W, A> Reg<W, A>
+where
+    W: Width,
+    A: Readable,
+{
+    pub fn read<M>(&self, mmio: &M) -> Result<W, M::Error>
+    where
+        M: MMIO,
+    {
+        W::read(mmio, self.space, self.offset)
+    }
+}
+
+impl<W, A> Reg<W, A>
+where
+    W: Width,
+    A: Writable,
+{
+    pub fn write<M>(&self, mmio: &mut M, value: W) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        W::write(mmio, self.space, self.offset, value)
+    }
+}
+
+impl<W> Reg<W, RW>
+where
+    W: Width,
+{
+    /// Reads the register, applies `f` and writes back the result.
+    pub fn modify<M, F>(&self, mmio: &mut M, f: F) -> Result<(), M::Error>
+    where
+        M: MMIO,
+        F: FnOnce(W) -> W,
+    {
+        let value = self.read(mmio)?;
+        self.write(mmio, f(value))
+    }
+}
+
+impl<W> Reg<W, W1C>
+where
+    W: Width,
+{
+    /// Clears the bits set in `mask`.
+    pub fn clear<M>(&self, mmio: &mut M, mask: W) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        W::write(mmio, self.space, self.offset, mask)
+    }
+}
+
+impl<W, A> Clone for Reg<W, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W, A> Copy for Reg<W, A> {}
+
+impl<W, A> Debug for Reg<W, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reg")
+            .field("space", &self.space)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+/// A bit field of `len` bits starting at bit `shift` of a register of width
+/// `W`, with access mode `A`.
+pub struct Field<W, A> {
+    reg: Reg<W, A>,
+    shift: u32,
+    len: u32,
+}
+
+impl<W, A> Field<W, A> {
+    /// Returns the field of `len` bits starting at bit `shift` of the register
+    /// at `offset` in address space `space`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is empty or exceeds 64 bits, which fails to compile
+    /// when evaluated in a const context.
+    pub const fn new(space: u32, offset: usize, shift: u32, len: u32) -> Self {
+        assert!(
+            len > 0 && len <= 64 && shift <= 64 - len,
+            "invalid field bit range"
+        );
+        Self {
+            reg: Reg::new(space, offset),
+            shift,
+            len,
+        }
+    }
+
+    pub fn shift(&self) -> u32 {
+        self.shift
+    }
+
+    pub fn len(&self) -> u32 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the mask of this field in the register.
+    pub fn mask(&self) -> u64 {
+        (u64::MAX >> (64 - self.len)) << self.shift
+    }
+}
+
+impl<W, A> Field<W, A>
+where
+    W: Width,
+    A: Readable,
+{
+    /// Returns the value of this field, shifted to the least significant bits.
+    pub fn read<M>(&self, mmio: &M) -> Result<W, M::Error>
+    where
+        M: MMIO,
+    {
+        self.reg
+            .read(mmio)
+            .map(|value| W::from_u64((value.to_u64() & self.mask()) >> self.shift))
+    }
+
+    /// Returns true if any bit of this field is set.
+    pub fn is_set<M>(&self, mmio: &M) -> Result<bool, M::Error>
+    where
+        M: MMIO,
+    {
+        self.read(mmio).map(|value| value.to_u64() != 0)
+    }
+}
+
+impl<W> Field<W, RW>
+where
+    W: Width,
+{
+    /// Writes `value` to this field, preserving the other bits of the register.
+    /// Bits of `value` that don't fit in the field are ignored.
+    pub fn write<M>(&self, mmio: &mut M, value: W) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        let mask = self.mask();
+        self.reg.modify(mmio, |current| {
+            W::from_u64((current.to_u64() & !mask) | ((value.to_u64() << self.shift) & mask))
+        })
+    }
+}
+
+impl<W> Field<W, WO>
+where
+    W: Width,
+{
+    /// Writes `value` to this field. The register can't be read, so all other
+    /// bits of the register are written as zero.
+    pub fn write<M>(&self, mmio: &mut M, value: W) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.reg.write(
+            mmio,
+            W::from_u64((value.to_u64() << self.shift) & self.mask()),
+        )
+    }
+}
+
+impl<W> Field<W, W1C>
+where
+    W: Width,
+{
+    /// Clears all bits of this field, without affecting other bits of the
+    /// register.
+    pub fn clear<M>(&self, mmio: &mut M) -> Result<(), M::Error>
+    where
+        M: MMIO,
+    {
+        self.reg.clear(mmio, W::from_u64(self.mask()))
+    }
+}
+
+impl<W, A> Clone for Field<W, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<W, A> Copy for Field<W, A> {}
+
+impl<W, A> Debug for Field<W, A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Field")
+            .field("space", &self.reg.space)
+            .field("offset", &self.reg.offset)
+            .field("shift", &self.shift)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fpga_sim::Sim;
+    use std::convert::TryInto;
+
+    #[derive(RegisterBlock)]
+    struct Control {
+        #[register(offset = 0x00)]
+        ctrl: Reg<u32, RW>,
+        #[field(offset = 0x00, bit = 0)]
+        start: Field<u32, RW>,
+        #[field(offset = 0x00, bit = 1)]
+        done: Field<u32, RO>,
+        #[register(offset = 0x08)]
+        isr: Reg<u32, W1C>,
+        #[field(offset = 0x08, bit = 1)]
+        isr_ready: Field<u32, W1C>,
+        #[field(offset = 0x0c, bits = "4..12")]
+        mode: Field<u32, RW>,
+        #[register(offset = 0x10)]
+        arg: Reg<u64, WO>,
+    }
+
+    #[test]
+    fn derive() {
+        assert_eq!(Control::SIZE, 0x18);
+
+        let control = Control::new(0, 0x100);
+        assert_eq!(control.ctrl.offset(), 0x100);
+        assert_eq!(control.arg.offset(), 0x110);
+        assert_eq!(control.mode.shift(), 4);
+        assert_eq!(control.mode.len(), 8);
+        assert_eq!(control.mode.mask(), 0xff0);
+    }
+
+    #[test]
+    fn access() {
+        let mut sim = Sim::new();
+        // Model write-1-to-clear behaviour of the interrupt status register.
+        sim.on_write(0x108..0x10c, |registers, offset, data| {
+            let mut current = [0; 4];
+            registers.read(offset, &mut current).unwrap();
+            let mask = u32::from_le_bytes(data.try_into().unwrap());
+            let value = u32::from_le_bytes(current) & !mask;
+            registers.write(offset, &value.to_le_bytes()).unwrap();
+        });
+        let control = Control::at(0, 0x100);
+
+        control.ctrl.write(&mut sim, 0b10).unwrap();
+        control.start.write(&mut sim, 1).unwrap();
+        assert_eq!(control.ctrl.read(&sim).unwrap(), 0b11);
+        assert!(control.done.is_set(&sim).unwrap());
+
+        control.mode.write(&mut sim, 0x1ab).unwrap();
+        assert_eq!(control.mode.read(&sim).unwrap(), 0xab);
+        assert_eq!(sim.registers().read_u32(0x10c).unwrap(), 0xab0);
+
+        sim.registers_mut().write_u32(0x108, 0b11).unwrap();
+        control.isr_ready.clear(&mut sim).unwrap();
+        assert_eq!(control.isr.read(&sim).unwrap(), 0b01);
+        control.isr.clear(&mut sim, 0b01).unwrap();
+        assert_eq!(control.isr.read(&sim).unwrap(), 0);
+
+        control.arg.write(&mut sim, u64::MAX).unwrap();
+        assert_eq!(sim.registers().read_u32(0x114).unwrap(), u32::MAX);
+    }
+
+    #[test]
+    fn field_bounds() {
+        const FULL: Field<u64, RW> = Field::new(0, 0, 0, 64);
+        assert_eq!(FULL.mask(), u64::MAX);
+        assert_eq!(Field::<u64, RW>::new(0, 0, 63, 1).mask(), 1 << 63);
+        assert_eq!(Field::<u64, RW>::new(0, 0, 0, 1).mask(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid field bit range")]
+    fn field_empty() {
+        Field::<u32, RW>::new(0, 0, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid field bit range")]
+    fn field_out_of_range() {
+        Field::<u64, RW>::new(0, 0, 60, 8);
+    }
+}
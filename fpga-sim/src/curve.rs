@@ -0,0 +1,59 @@
+use std::fmt::{self, Debug, Formatter};
+
+/// A deterministic curve sampled at the current tick of the simulated clock.
+/// Used to model power usage and temperature of a simulated device.
+pub enum Curve {
+    /// A constant value.
+    Constant(f32),
+    /// Starts at `initial` and changes by `slope` per tick.
+    Linear { initial: f32, slope: f32 },
+    /// Starts at `initial` and exponentially approaches `target`, closing
+    /// `rate` (between 0 and 1) of the remaining distance every tick.
+    Approach {
+        initial: f32,
+        target: f32,
+        rate: f32,
+    },
+    /// A user provided function of the current tick.
+    Custom(Box<dyn Fn(u64) -> f32 + Send>),
+}
+
+impl Curve {
+    /// Returns the value of this curve at `tick`.
+    pub fn sample(&self, tick: u64) -> f32 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Linear { initial, slope } => initial + slope * tick as f32,
+            Self::Approach {
+                initial,
+                target,
+                rate,
+            } => target + (initial - target) * (1. - rate).powf(tick as f32),
+            Self::Custom(f) => f(tick),
+        }
+    }
+}
+
+impl Debug for Curve {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constant(value) => f.debug_tuple("Constant").field(value).finish(),
+            Self::Linear { initial, slope } => f
+                .debug_struct("Linear")
+                .field("initial", initial)
+                .field("slope", slope)
+                .finish(),
+            Self::Approach {
+                initial,
+                target,
+                rate,
+            } => f
+                .debug_struct("Approach")
+                .field("initial", initial)
+                .field("target", target)
+                .field("rate", rate)
+                .finish(),
+            Self::Custom(_) => f.debug_tuple("Custom").finish_non_exhaustive(),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use fpga_core::MmioError;
+
+/// Errors returned by the simulated platform.
+pub type Error = MmioError;
+
+/// Result type with `Error` error type.
+pub type Result<T> = std::result::Result<T, Error>;
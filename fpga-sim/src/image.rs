@@ -0,0 +1,94 @@
+use crate::{registers::Hooks, Curve, RegisterFile};
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::Range,
+};
+use uuid::Uuid;
+
+/// A simulated device image, used as source to program a [crate::Sim].
+///
+/// Programming an image resets the register file, applies the reset values of
+/// the image and replaces the register behaviour of the device with the hooks
+/// of the image.
+pub struct Image {
+    pub(crate) uuid: Uuid,
+    pub(crate) reset_values: Vec<(usize, Vec<u8>)>,
+    pub(crate) hooks: Hooks,
+    pub(crate) power: Option<Curve>,
+    pub(crate) temperature: Option<Curve>,
+}
+
+impl Image {
+    /// Returns a new image identified by `uuid`, without reset values or
+    /// register behaviour.
+    pub fn new(uuid: Uuid) -> Self {
+        Self {
+            uuid,
+            reset_values: Vec::default(),
+            hooks: Hooks::default(),
+            power: None,
+            temperature: None,
+        }
+    }
+
+    pub fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    /// Sets the bytes at `offset` to `data` when this image is programmed.
+    pub fn with_reset_value<T>(mut self, offset: usize, data: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.reset_values.push((offset, data.as_ref().to_vec()));
+        self
+    }
+
+    /// Sets the 32-bit register at `offset` to `value` when this image is
+    /// programmed.
+    pub fn with_reset_u32(self, offset: usize, value: u32) -> Self {
+        self.with_reset_value(offset, value.to_le_bytes())
+    }
+
+    /// Adds a read hook for reads starting in `range`.
+    pub fn on_read<F>(mut self, range: Range<usize>, hook: F) -> Self
+    where
+        F: Fn(&RegisterFile, usize, &mut [u8]) + Send + 'static,
+    {
+        self.hooks.on_read(range, Box::new(hook));
+        self
+    }
+
+    /// Adds a write hook for writes starting in `range`.
+    pub fn on_write<F>(mut self, range: Range<usize>, hook: F) -> Self
+    where
+        F: FnMut(&mut RegisterFile, usize, &[u8]) + Send + 'static,
+    {
+        self.hooks.on_write(range, Box::new(hook));
+        self
+    }
+
+    /// Replaces the power curve of the device when this image is programmed.
+    pub fn with_power(mut self, power: Curve) -> Self {
+        self.power.replace(power);
+        self
+    }
+
+    /// Replaces the temperature curve of the device when this image is
+    /// programmed.
+    pub fn with_temperature(mut self, temperature: Curve) -> Self {
+        self.temperature.replace(temperature);
+        self
+    }
+}
+
+impl Debug for Image {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Image")
+            .field("uuid", &self.uuid)
+            .field("hooks", &self.hooks)
+            .field("power", &self.power)
+            .field("temperature", &self.temperature)
+            .finish_non_exhaustive()
+    }
+}
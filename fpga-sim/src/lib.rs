@@ -0,0 +1,398 @@
+use fpga_core::{
+    DynMMIO, DynPower, DynProgram, DynThermal, Platform, PlatformType, Power, Program, Thermal,
+    MMIO,
+};
+use std::{fmt::Debug, ops::Range};
+use uuid::Uuid;
+
+mod curve;
+pub use curve::*;
+
+mod errors;
+pub use errors::*;
+
+mod image;
+pub use image::*;
+
+mod registers;
+use registers::Hooks;
+pub use registers::{ReadHook, RegisterFile, WriteHook};
+
+/// Configuration of a simulated device.
+#[derive(Debug)]
+pub struct Configuration {
+    /// Size of the register file in bytes.
+    pub size: usize,
+    /// Power usage in Watts.
+    pub power: Curve,
+    /// Temperature in degrees C.
+    pub temperature: Curve,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Self {
+            size: 0x1000,
+            power: Curve::Constant(0.),
+            temperature: Curve::Constant(25.),
+        }
+    }
+}
+
+impl Configuration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_power(mut self, power: Curve) -> Self {
+        self.power = power;
+        self
+    }
+
+    pub fn with_temperature(mut self, temperature: Curve) -> Self {
+        self.temperature = temperature;
+        self
+    }
+}
+
+/// An in-memory simulated device.
+///
+/// The simulation is deterministic: power and temperature are sampled from
+/// their curves at the current tick of a simulated clock, which only moves
+/// forward when [Sim::advance] is called.
+pub struct Sim {
+    registers: RegisterFile,
+    hooks: Hooks,
+    power: Curve,
+    temperature: Curve,
+    ticks: u64,
+    uuid: Option<Uuid>,
+}
+
+impl Sim {
+    /// Returns a simulated device with the default configuration.
+    pub fn new() -> Self {
+        Self::from_configuration(Configuration::default()).unwrap()
+    }
+
+    /// Returns the register file of this device.
+    pub fn registers(&self) -> &RegisterFile {
+        &self.registers
+    }
+
+    /// Returns the register file of this device. Accesses through the
+    /// returned reference bypass the register hooks.
+    pub fn registers_mut(&mut self) -> &mut RegisterFile {
+        &mut self.registers
+    }
+
+    /// Adds a read hook for reads starting in `range`.
+    pub fn on_read<F>(&mut self, range: Range<usize>, hook: F)
+    where
+        F: Fn(&RegisterFile, usize, &mut [u8]) + Send + 'static,
+    {
+        self.hooks.on_read(range, Box::new(hook));
+    }
+
+    /// Adds a write hook for writes starting in `range`.
+    pub fn on_write<F>(&mut self, range: Range<usize>, hook: F)
+    where
+        F: FnMut(&mut RegisterFile, usize, &[u8]) + Send + 'static,
+    {
+        self.hooks.on_write(range, Box::new(hook));
+    }
+
+    /// Advances the simulated clock by `ticks`.
+    pub fn advance(&mut self, ticks: u64) {
+        self.ticks = self.ticks.saturating_add(ticks);
+    }
+
+    /// Returns the current tick of the simulated clock.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the UUID of the programmed image, if any.
+    pub fn uuid(&self) -> Option<Uuid> {
+        self.uuid
+    }
+}
+
+impl Default for Sim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Sim {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sim")
+            .field("registers", &self.registers)
+            .field("hooks", &self.hooks)
+            .field("power", &self.power)
+            .field("temperature", &self.temperature)
+            .field("ticks", &self.ticks)
+            .field("uuid", &self.uuid)
+            .finish()
+    }
+}
+
+impl Platform for Sim {
+    type Configuration = Configuration;
+    type Error = Error;
+
+    fn platform(&self) -> PlatformType {
+        PlatformType::SIM
+    }
+
+    fn from_configuration(configuration: Self::Configuration) -> Result<Self> {
+        Ok(Self {
+            registers: RegisterFile::new(configuration.size),
+            hooks: Hooks::default(),
+            power: configuration.power,
+            temperature: configuration.temperature,
+            ticks: 0,
+            uuid: None,
+        })
+    }
+
+    fn dyn_mmio(&self) -> Option<&dyn DynMMIO> {
+        Some(self)
+    }
+
+    fn dyn_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        Some(self)
+    }
+
+    fn dyn_power(&self) -> Option<&dyn DynPower> {
+        Some(self)
+    }
+
+    fn dyn_thermal(&self) -> Option<&dyn DynThermal> {
+        Some(self)
+    }
+
+    fn dyn_program(&mut self) -> Option<&mut dyn DynProgram> {
+        Some(self)
+    }
+}
+
+/// The register file is the only address space of a simulated device.
+impl MMIO for Sim {
+    fn num_spaces(&self) -> u32 {
+        1
+    }
+
+    fn space_size(&self, space: u32) -> Option<usize> {
+        Some(space)
+            .filter(|&space| space < self.num_spaces())
+            .map(|_| self.registers.len())
+    }
+
+    fn read_u32(&self, space: u32, offset: usize) -> Result<u32> {
+        self.check_access(space, offset, 4, 4)?;
+        let mut data = [0; 4];
+        self.hooks.read(&self.registers, offset, &mut data)?;
+        Ok(u32::from_le_bytes(data))
+    }
+
+    fn read_u64(&self, space: u32, offset: usize) -> Result<u64> {
+        self.check_access(space, offset, 8, 8)?;
+        let mut data = [0; 8];
+        self.hooks.read(&self.registers, offset, &mut data)?;
+        Ok(u64::from_le_bytes(data))
+    }
+
+    fn write_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<()> {
+        self.check_access(space, offset, 4, 4)?;
+        self.hooks
+            .write(&mut self.registers, offset, &value.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<()> {
+        self.check_access(space, offset, 8, 8)?;
+        self.hooks
+            .write(&mut self.registers, offset, &value.to_le_bytes())
+    }
+
+    fn read_into(&self, space: u32, offset: usize, data: &mut [u32]) -> Result<()> {
+        self.check_access(space, offset, data.len() * 4, 4)?;
+        data.iter_mut().enumerate().try_for_each(|(index, value)| {
+            *value = self.read_u32(space, offset + index * 4)?;
+            Ok(())
+        })
+    }
+
+    fn write_from(&mut self, space: u32, offset: usize, data: &[u32]) -> Result<()> {
+        self.check_access(space, offset, data.len() * 4, 4)?;
+        data.iter()
+            .enumerate()
+            .try_for_each(|(index, &value)| self.write_u32(space, offset + index * 4, value))
+    }
+}
+
+impl Power for Sim {
+    fn power(&self) -> Result<f32> {
+        Ok(self.power.sample(self.ticks))
+    }
+}
+
+impl Thermal for Sim {
+    fn temperature(&self) -> Result<f32> {
+        Ok(self.temperature.sample(self.ticks))
+    }
+}
+
+impl Program for Sim {
+    type Source = Image;
+    type Output = Uuid;
+
+    fn program(&mut self, source: Self::Source) -> Result<Self::Output> {
+        let mut registers = RegisterFile::new(self.registers.len());
+        source
+            .reset_values
+            .iter()
+            .try_for_each(|(offset, data)| registers.write(*offset, data))?;
+
+        self.registers = registers;
+        self.hooks = source.hooks;
+        if let Some(power) = source.power {
+            self.power = power;
+        }
+        if let Some(temperature) = source.temperature {
+            self.temperature = temperature;
+        }
+        self.uuid.replace(source.uuid);
+
+        Ok(source.uuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_write() {
+        let mut sim = Sim::new();
+        sim.write_u32(0, 0x10, 0x0403_0201).unwrap();
+        assert_eq!(sim.read_u32(0, 0x10).unwrap(), 0x0403_0201);
+        assert_eq!(sim.registers().read_u32(0x10).unwrap(), 0x0403_0201);
+        sim.write_u64(0, 0x18, u64::MAX).unwrap();
+        assert_eq!(sim.read_u64(0, 0x18).unwrap(), u64::MAX);
+
+        sim.write_from(0, 0x20, &[1, 2, 3]).unwrap();
+        let mut data = [0; 3];
+        sim.read_into(0, 0x20, &mut data).unwrap();
+        assert_eq!(data, [1, 2, 3]);
+
+        assert_eq!(
+            sim.read_u32(0, 0x12),
+            Err(Error::Misaligned {
+                offset: 0x12,
+                align: 4
+            })
+        );
+        assert_eq!(
+            sim.read_u32(1, 0x10),
+            Err(Error::InvalidSpace {
+                space: 1,
+                num_spaces: 1
+            })
+        );
+        assert!(sim.read_u64(0, 0x1000).is_err());
+        // Bulk accesses are checked before any register is written.
+        assert!(sim.write_from(0, 0xff8, &[4, 5, 6]).is_err());
+        assert_eq!(sim.read_u32(0, 0xff8).unwrap(), 0);
+    }
+
+    #[test]
+    fn hooks() {
+        let mut sim = Sim::new();
+        // Writing to the control register sets the done bit in the status register.
+        sim.on_write(0x0..0x4, |registers, _, _| {
+            registers.write_u32(0x4, 1).unwrap();
+        });
+        // Reads of the version register always return 7.
+        sim.on_read(0x8..0xc, |_, _, data| {
+            data.copy_from_slice(&7u32.to_le_bytes())
+        });
+
+        sim.write_u32(0, 0x0, 1).unwrap();
+        assert_eq!(sim.registers().read_u32(0x0).unwrap(), 0);
+        assert_eq!(sim.read_u32(0, 0x4).unwrap(), 1);
+        assert_eq!(sim.read_u32(0, 0x8).unwrap(), 7);
+    }
+
+    #[test]
+    fn program() {
+        let mut sim = Sim::new();
+        sim.write_u32(0, 0x20, 0xff).unwrap();
+
+        let uuid = Uuid::from_u128(1);
+        let image = Image::new(uuid)
+            .with_reset_u32(0x0, 0xdead_beef)
+            .with_power(Curve::Linear {
+                initial: 10.,
+                slope: 0.5,
+            });
+        assert_eq!(sim.program(image).unwrap(), uuid);
+        assert_eq!(sim.uuid(), Some(uuid));
+        assert_eq!(sim.registers().read_u32(0x0).unwrap(), 0xdead_beef);
+        assert_eq!(sim.read_u32(0, 0x20).unwrap(), 0);
+
+        assert_eq!(sim.power().unwrap(), 10.);
+        sim.advance(4);
+        assert_eq!(sim.power().unwrap(), 12.);
+        assert_eq!(sim.temperature().unwrap(), 25.);
+
+        assert!(sim
+            .program(Image::new(uuid).with_reset_value(0x1000, [0]))
+            .is_err());
+    }
+
+    #[test]
+    fn dynamic() {
+        use fpga_core::DynPlatform;
+
+        let mut platform: Box<dyn DynPlatform> = Box::new(Sim::new());
+        assert_eq!(platform.platform_type(), PlatformType::SIM);
+
+        let mmio = platform.as_mmio_mut().unwrap();
+        mmio.store_from(0, 0x8, &[1; 3]).unwrap();
+        let mut data = [0; 3];
+        mmio.load_into(0, 0x8, &mut data).unwrap();
+        assert_eq!(data, [1; 3]);
+        assert!(matches!(
+            mmio.load_into(0, 0xffc, &mut data),
+            Err(fpga_core::Error::InvalidParam(_))
+        ));
+
+        assert_eq!(
+            platform
+                .as_thermal()
+                .unwrap()
+                .current_temperature()
+                .unwrap(),
+            25.
+        );
+
+        let uuid = Uuid::from_u128(2);
+        let program = platform.as_program().unwrap();
+        assert!(matches!(
+            program.program_any(Box::new(uuid)),
+            Err(fpga_core::Error::InvalidParam(_))
+        ));
+        let output = program.program_any(Box::new(Image::new(uuid))).unwrap();
+        assert_eq!(output.downcast_ref::<Uuid>(), Some(&uuid));
+        assert_eq!(
+            platform.as_any().downcast_ref::<Sim>().unwrap().uuid(),
+            Some(uuid)
+        );
+    }
+}
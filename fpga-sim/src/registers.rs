@@ -0,0 +1,162 @@
+use crate::{Error, Result};
+use std::{
+    convert::TryInto,
+    fmt::{self, Debug, Formatter},
+    ops::Range,
+};
+
+/// Invoked after the bytes of a read are copied from the register file.
+/// Receives the register file, the offset of the read and the output bytes,
+/// which may be modified.
+pub type ReadHook = Box<dyn Fn(&RegisterFile, usize, &mut [u8]) + Send>;
+
+/// Invoked instead of storing the bytes of a write in the register file.
+/// Receives the register file, the offset of the write and the written bytes.
+pub type WriteHook = Box<dyn FnMut(&mut RegisterFile, usize, &[u8]) + Send>;
+
+/// The in-memory register file of a simulated device.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RegisterFile(Vec<u8>);
+
+impl RegisterFile {
+    /// Returns a zero-initialized register file of `size` bytes.
+    pub fn new(size: usize) -> Self {
+        Self(vec![0; size])
+    }
+
+    /// Returns the size of this register file in bytes.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if this register file has a size of zero bytes.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn range(&self, offset: usize, len: usize) -> Result<Range<usize>> {
+        offset
+            .checked_add(len)
+            .filter(|&end| end <= self.len())
+            .map(|end| offset..end)
+            .ok_or(Error::OutOfBounds {
+                offset,
+                len,
+                size: self.len(),
+            })
+    }
+
+    /// Copies the bytes at `offset` into `data`.
+    pub fn read(&self, offset: usize, data: &mut [u8]) -> Result<()> {
+        let range = self.range(offset, data.len())?;
+        data.copy_from_slice(&self.0[range]);
+        Ok(())
+    }
+
+    /// Copies `data` into the register file at `offset`.
+    pub fn write(&mut self, offset: usize, data: &[u8]) -> Result<()> {
+        let range = self.range(offset, data.len())?;
+        self.0[range].copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Returns the little-endian 32-bit value at `offset`.
+    pub fn read_u32(&self, offset: usize) -> Result<u32> {
+        let range = self.range(offset, 4)?;
+        Ok(u32::from_le_bytes(self.0[range].try_into().unwrap()))
+    }
+
+    /// Stores `value` as little-endian 32-bit value at `offset`.
+    pub fn write_u32(&mut self, offset: usize, value: u32) -> Result<()> {
+        self.write(offset, &value.to_le_bytes())
+    }
+
+    /// Sets all bytes of the register file to zero.
+    pub fn clear(&mut self) {
+        self.0.iter_mut().for_each(|byte| *byte = 0);
+    }
+}
+
+impl Debug for RegisterFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisterFile")
+            .field("size", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The register behaviour callbacks of a simulated device.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    read: Vec<(Range<usize>, ReadHook)>,
+    write: Vec<(Range<usize>, WriteHook)>,
+}
+
+impl Hooks {
+    pub(crate) fn on_read(&mut self, range: Range<usize>, hook: ReadHook) {
+        self.read.push((range, hook));
+    }
+
+    pub(crate) fn on_write(&mut self, range: Range<usize>, hook: WriteHook) {
+        self.write.push((range, hook));
+    }
+
+    /// Reads `data` from `registers` at `offset`, applying the read hooks that
+    /// contain `offset` in order of registration.
+    pub(crate) fn read(
+        &self,
+        registers: &RegisterFile,
+        offset: usize,
+        data: &mut [u8],
+    ) -> Result<()> {
+        registers.read(offset, data)?;
+        self.read
+            .iter()
+            .filter(|(range, _)| range.contains(&offset))
+            .for_each(|(_, hook)| hook(registers, offset, data));
+        Ok(())
+    }
+
+    /// Writes `data` to `registers` at `offset`. If a write hook contains
+    /// `offset` the first one registered handles the write, otherwise the
+    /// bytes are stored as-is.
+    pub(crate) fn write(
+        &mut self,
+        registers: &mut RegisterFile,
+        offset: usize,
+        data: &[u8],
+    ) -> Result<()> {
+        // Check bounds before handing the write to a hook.
+        registers.range(offset, data.len())?;
+        match self
+            .write
+            .iter_mut()
+            .find(|(range, _)| range.contains(&offset))
+        {
+            Some((_, hook)) => {
+                hook(registers, offset, data);
+                Ok(())
+            }
+            None => registers.write(offset, data),
+        }
+    }
+}
+
+impl Debug for Hooks {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hooks")
+            .field(
+                "read",
+                &self.read.iter().map(|(range, _)| range).collect::<Vec<_>>(),
+            )
+            .field(
+                "write",
+                &self
+                    .write
+                    .iter()
+                    .map(|(range, _)| range)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
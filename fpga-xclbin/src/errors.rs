@@ -0,0 +1,39 @@
+use crate::SectionKind;
+
+/// Errors returned when parsing an xclbin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with the `xclbin2` magic.
+    InvalidMagic,
+    /// A structure at `offset` of `len` bytes exceeds the data of `size`
+    /// bytes.
+    Truncated {
+        offset: usize,
+        len: usize,
+        size: usize,
+    },
+    /// A section of this kind is malformed.
+    InvalidSection(SectionKind),
+    /// A text section is not valid UTF-8.
+    InvalidText(SectionKind),
+}
+
+impl std::error::Error for Error {}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "invalid xclbin magic"),
+            Self::Truncated { offset, len, size } => write!(
+                f,
+                "structure of {} bytes at offset {:#x} exceeds xclbin of {} bytes",
+                len, offset, size
+            ),
+            Self::InvalidSection(kind) => write!(f, "invalid {:?} section", kind),
+            Self::InvalidText(kind) => write!(f, "{:?} section is not valid UTF-8", kind),
+        }
+    }
+}
+
+/// Result type with `Error` error type.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,319 @@
+//! Pure-Rust parser of Xilinx xclbin (axlf) container files.
+//!
+//! The parser reads the axlf header, the section table and the metadata
+//! sections, without requiring XRT to be installed.
+
+use std::convert::TryFrom;
+use uuid::Uuid;
+
+mod errors;
+pub use errors::*;
+
+mod reader;
+use reader::Reader;
+
+mod section;
+pub use section::*;
+
+mod topology;
+pub use topology::*;
+
+/// Magic at the start of every xclbin.
+pub const MAGIC: &[u8; 8] = b"xclbin2\0";
+
+/// Offset of the axlf header in an xclbin.
+const HEADER_OFFSET: usize = 304;
+
+/// Offset of the section table in an xclbin.
+const SECTIONS_OFFSET: usize = HEADER_OFFSET + 152;
+
+/// Size of an entry of the section table.
+const SECTION_HEADER_SIZE: usize = 40;
+
+/// The axlf header of an xclbin (`axlf_header`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// Length of the signature, or -1 if the xclbin is not signed.
+    pub signature_length: i32,
+    pub unique_id: u64,
+    /// Total size of the xclbin in bytes.
+    pub length: u64,
+    pub timestamp: u64,
+    pub feature_rom_timestamp: u64,
+    pub version_major: u8,
+    pub version_minor: u8,
+    pub version_patch: u16,
+    pub mode: u32,
+    /// UUID of the feature ROM of the target platform.
+    pub rom_uuid: Uuid,
+    /// Name of the target platform.
+    pub platform_vbnv: String,
+    pub uuid: Uuid,
+    pub debug_bin: String,
+}
+
+/// A parsed xclbin, borrowing the underlying data.
+#[derive(Clone, Debug)]
+pub struct Axlf<'data> {
+    data: &'data [u8],
+    header: Header,
+    sections: Vec<SectionHeader>,
+}
+
+impl<'data> Axlf<'data> {
+    /// Parses the header and section table of the xclbin in `data`.
+    pub fn parse(data: &'data [u8]) -> Result<Self> {
+        let reader = Reader::new(data);
+        if reader.bytes(0, MAGIC.len())? != MAGIC {
+            return Err(Error::InvalidMagic);
+        }
+
+        let header = Header {
+            signature_length: reader.i32(8)?,
+            unique_id: reader.u64(296)?,
+            length: reader.u64(HEADER_OFFSET)?,
+            timestamp: reader.u64(HEADER_OFFSET + 8)?,
+            feature_rom_timestamp: reader.u64(HEADER_OFFSET + 16)?,
+            version_patch: reader.u16(HEADER_OFFSET + 24)?,
+            version_major: reader.u8(HEADER_OFFSET + 26)?,
+            version_minor: reader.u8(HEADER_OFFSET + 27)?,
+            mode: reader.u32(HEADER_OFFSET + 28)?,
+            rom_uuid: Uuid::from_bytes(reader.array(HEADER_OFFSET + 32)?),
+            platform_vbnv: reader.str(HEADER_OFFSET + 48, 64)?,
+            uuid: Uuid::from_bytes(reader.array(HEADER_OFFSET + 112)?),
+            debug_bin: reader.str(HEADER_OFFSET + 128, 16)?,
+        };
+
+        let count = reader.entries(
+            reader.u32(HEADER_OFFSET + 144)?.into(),
+            SECTIONS_OFFSET,
+            SECTION_HEADER_SIZE,
+        )?;
+        let sections = (0..count)
+            .map(|index| {
+                let offset = SECTIONS_OFFSET + index * SECTION_HEADER_SIZE;
+                let section = SectionHeader {
+                    kind: reader.u32(offset)?.into(),
+                    name: reader.str(offset + 4, 16)?,
+                    offset: reader.u64(offset + 24)?,
+                    size: reader.u64(offset + 32)?,
+                };
+                // Check the section is in bounds, so it can be sliced later.
+                let start = usize::try_from(section.offset).unwrap_or(usize::MAX);
+                let len = usize::try_from(section.size).unwrap_or(usize::MAX);
+                reader.bytes(start, len)?;
+                Ok(section)
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            data,
+            header,
+            sections,
+        })
+    }
+
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Returns the UUID of this xclbin.
+    pub fn uuid(&self) -> Uuid {
+        self.header.uuid
+    }
+
+    /// Returns the section table.
+    pub fn sections(&self) -> &[SectionHeader] {
+        &self.sections
+    }
+
+    /// Returns the data of the first section of `kind`, if any.
+    pub fn section(&self, kind: SectionKind) -> Option<&'data [u8]> {
+        self.sections
+            .iter()
+            .find(|section| section.kind == kind)
+            .map(|section| {
+                let start = section.offset as usize;
+                &self.data[start..start + section.size as usize]
+            })
+    }
+
+    /// Parses the first section of `kind` with `parse`, if any.
+    fn parse_section<T, F>(&self, kind: SectionKind, parse: F) -> Result<Option<T>>
+    where
+        F: FnOnce(&'data [u8]) -> Result<T>,
+    {
+        self.section(kind)
+            .map(|data| parse(data).map_err(|_| Error::InvalidSection(kind)))
+            .transpose()
+    }
+
+    /// Returns the text of the first section of `kind`, if any.
+    fn text_section(&self, kind: SectionKind) -> Result<Option<&'data str>> {
+        self.section(kind)
+            .map(|data| {
+                // Text sections may be nul-terminated.
+                let end = data
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .unwrap_or(data.len());
+                std::str::from_utf8(&data[..end]).map_err(|_| Error::InvalidText(kind))
+            })
+            .transpose()
+    }
+
+    /// Returns the memory banks of the MEM_TOPOLOGY section.
+    pub fn mem_topology(&self) -> Result<Option<Vec<MemData>>> {
+        self.parse_section(SectionKind::MemTopology, topology::mem_topology)
+    }
+
+    /// Returns the connections of kernel arguments to memory banks of the
+    /// CONNECTIVITY section.
+    pub fn connectivity(&self) -> Result<Option<Vec<Connection>>> {
+        self.parse_section(SectionKind::Connectivity, topology::connectivity)
+    }
+
+    /// Returns the IPs of the IP_LAYOUT section.
+    pub fn ip_layout(&self) -> Result<Option<Vec<IpData>>> {
+        self.parse_section(SectionKind::IpLayout, topology::ip_layout)
+    }
+
+    /// Returns the clocks of the CLOCK_FREQ_TOPOLOGY section.
+    pub fn clock_freq_topology(&self) -> Result<Option<Vec<ClockFreq>>> {
+        self.parse_section(
+            SectionKind::ClockFreqTopology,
+            topology::clock_freq_topology,
+        )
+    }
+
+    /// Returns the JSON of the BUILD_METADATA section.
+    pub fn build_metadata(&self) -> Result<Option<&'data str>> {
+        self.text_section(SectionKind::BuildMetadata)
+    }
+
+    /// Returns the XML of the EMBEDDED_METADATA section.
+    pub fn embedded_metadata(&self) -> Result<Option<&'data str>> {
+        self.text_section(SectionKind::EmbeddedMetadata)
+    }
+
+    /// Returns the BITSTREAM section.
+    pub fn bitstream(&self) -> Option<&'data [u8]> {
+        self.section(SectionKind::Bitstream)
+    }
+
+    /// Returns the PDI section.
+    pub fn pdi(&self) -> Option<&'data [u8]> {
+        self.section(SectionKind::Pdi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns an xclbin with `sections` of kind and data.
+    fn xclbin(sections: &[(u32, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0; SECTIONS_OFFSET + sections.len() * SECTION_HEADER_SIZE];
+        data[..8].copy_from_slice(MAGIC);
+        data[8..12].copy_from_slice(&(-1i32).to_le_bytes());
+        data[HEADER_OFFSET + 26] = 2;
+        data[HEADER_OFFSET + 48..HEADER_OFFSET + 55].copy_from_slice(b"u250_xd");
+        data[HEADER_OFFSET + 112..HEADER_OFFSET + 128].copy_from_slice(&[1; 16]);
+        data[HEADER_OFFSET + 144..HEADER_OFFSET + 148]
+            .copy_from_slice(&(sections.len() as u32).to_le_bytes());
+        for (index, (kind, section)) in sections.iter().enumerate() {
+            let offset = data.len() as u64;
+            let header = SECTIONS_OFFSET + index * SECTION_HEADER_SIZE;
+            data[header..header + 4].copy_from_slice(&kind.to_le_bytes());
+            data[header + 24..header + 32].copy_from_slice(&offset.to_le_bytes());
+            data[header + 32..header + 40].copy_from_slice(&(section.len() as u64).to_le_bytes());
+            data.extend_from_slice(section);
+        }
+        let length = data.len() as u64;
+        data[HEADER_OFFSET..HEADER_OFFSET + 8].copy_from_slice(&length.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parse() {
+        let mut ip_layout = vec![0; 8 + 80];
+        ip_layout[..4].copy_from_slice(&1i32.to_le_bytes());
+        ip_layout[8..12].copy_from_slice(&1u32.to_le_bytes());
+        ip_layout[12..16].copy_from_slice(&0x0101u32.to_le_bytes());
+        ip_layout[16..24].copy_from_slice(&0x1800000u64.to_le_bytes());
+        ip_layout[24..39].copy_from_slice(b"vadd:{vadd_1}\0\0");
+
+        let mut mem_topology = vec![0; 8 + 40];
+        mem_topology[..4].copy_from_slice(&1i32.to_le_bytes());
+        mem_topology[8] = 1;
+        mem_topology[9] = 1;
+        mem_topology[16..24].copy_from_slice(&(16u64 << 20).to_le_bytes());
+        mem_topology[32..36].copy_from_slice(b"DDR0");
+
+        let data = xclbin(&[
+            (8, &ip_layout),
+            (6, &mem_topology),
+            (14, b"{\"build_metadata\":{}}\0"),
+            (0, &[0xff; 4]),
+        ]);
+        let axlf = Axlf::parse(&data).unwrap();
+
+        assert_eq!(axlf.header().signature_length, -1);
+        assert_eq!(axlf.header().version_major, 2);
+        assert_eq!(axlf.header().platform_vbnv, "u250_xd");
+        assert_eq!(axlf.header().length, data.len() as u64);
+        assert_eq!(axlf.uuid(), Uuid::from_bytes([1; 16]));
+        assert_eq!(axlf.sections().len(), 4);
+
+        let ips = axlf.ip_layout().unwrap().unwrap();
+        assert_eq!(ips[0].ip_type, IpType::Kernel);
+        assert_eq!(ips[0].name, "vadd:{vadd_1}");
+        assert_eq!(ips[0].base_address, 0x1800000);
+        assert!(ips[0].interrupt_enabled());
+        assert_eq!(
+            ips[0].control_protocol(),
+            Some(ControlProtocol::ApCtrlChain)
+        );
+        let ip = IpData {
+            properties: 0x0500,
+            ..ips[0].clone()
+        };
+        assert!(!ip.interrupt_enabled());
+        assert_eq!(ip.control_protocol(), Some(ControlProtocol::FastAdapter));
+
+        let mems = axlf.mem_topology().unwrap().unwrap();
+        assert_eq!(mems[0].mem_type, MemType::Ddr4);
+        assert!(mems[0].used);
+        assert_eq!(mems[0].size, 16 << 30);
+        assert_eq!(mems[0].tag, "DDR0");
+
+        assert_eq!(
+            axlf.build_metadata().unwrap(),
+            Some("{\"build_metadata\":{}}")
+        );
+        assert_eq!(axlf.bitstream(), Some(&[0xff; 4][..]));
+        assert_eq!(axlf.connectivity().unwrap(), None);
+        assert_eq!(axlf.pdi(), None);
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Axlf::parse(b"xclbin1\0").unwrap_err(), Error::InvalidMagic);
+        assert!(matches!(
+            Axlf::parse(MAGIC).unwrap_err(),
+            Error::Truncated { .. }
+        ));
+
+        // A section that exceeds the data.
+        let mut data = xclbin(&[(8, &[0; 8])]);
+        data.truncate(data.len() - 1);
+        assert!(Axlf::parse(&data).is_err());
+
+        // An IP_LAYOUT section with more entries than data.
+        let data = xclbin(&[(8, &[2, 0, 0, 0, 0, 0, 0, 0])]);
+        assert_eq!(
+            Axlf::parse(&data).unwrap().ip_layout(),
+            Err(Error::InvalidSection(SectionKind::IpLayout))
+        );
+    }
+}
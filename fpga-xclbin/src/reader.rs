@@ -0,0 +1,75 @@
+use crate::{Error, Result};
+use std::convert::{TryFrom, TryInto};
+
+/// Little-endian reader of the fixed-layout structures of an xclbin.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Reader<'data> {
+    data: &'data [u8],
+}
+
+impl<'data> Reader<'data> {
+    pub(crate) fn new(data: &'data [u8]) -> Self {
+        Self { data }
+    }
+
+    pub(crate) fn bytes(&self, offset: usize, len: usize) -> Result<&'data [u8]> {
+        offset
+            .checked_add(len)
+            .and_then(|end| self.data.get(offset..end))
+            .ok_or(Error::Truncated {
+                offset,
+                len,
+                size: self.data.len(),
+            })
+    }
+
+    pub(crate) fn array<const N: usize>(&self, offset: usize) -> Result<[u8; N]> {
+        Ok(self.bytes(offset, N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn u8(&self, offset: usize) -> Result<u8> {
+        self.array(offset).map(u8::from_le_bytes)
+    }
+
+    pub(crate) fn u16(&self, offset: usize) -> Result<u16> {
+        self.array(offset).map(u16::from_le_bytes)
+    }
+
+    pub(crate) fn i16(&self, offset: usize) -> Result<i16> {
+        self.array(offset).map(i16::from_le_bytes)
+    }
+
+    pub(crate) fn u32(&self, offset: usize) -> Result<u32> {
+        self.array(offset).map(u32::from_le_bytes)
+    }
+
+    pub(crate) fn i32(&self, offset: usize) -> Result<i32> {
+        self.array(offset).map(i32::from_le_bytes)
+    }
+
+    pub(crate) fn u64(&self, offset: usize) -> Result<u64> {
+        self.array(offset).map(u64::from_le_bytes)
+    }
+
+    /// Returns the nul-terminated string in the `len` bytes at `offset`.
+    pub(crate) fn str(&self, offset: usize, len: usize) -> Result<String> {
+        let bytes = self.bytes(offset, len)?;
+        let end = bytes.iter().position(|&byte| byte == 0).unwrap_or(len);
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    /// Returns the entry count of a section with `count` entries of `size`
+    /// bytes starting at `offset`, after checking they are in bounds.
+    pub(crate) fn entries(&self, count: i64, offset: usize, size: usize) -> Result<usize> {
+        let count = usize::try_from(count.max(0)).unwrap_or(usize::MAX);
+        count
+            .checked_mul(size)
+            .map(|len| self.bytes(offset, len))
+            .unwrap_or(Err(Error::Truncated {
+                offset,
+                len: usize::MAX,
+                size: self.data.len(),
+            }))
+            .map(|_| count)
+    }
+}
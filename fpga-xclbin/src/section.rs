@@ -0,0 +1,81 @@
+/// Kind of an xclbin section (`axlf_section_kind`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SectionKind {
+    Bitstream,
+    ClearingBitstream,
+    EmbeddedMetadata,
+    Firmware,
+    DebugData,
+    SchedFirmware,
+    MemTopology,
+    Connectivity,
+    IpLayout,
+    DebugIpLayout,
+    DesignCheckPoint,
+    ClockFreqTopology,
+    Mcs,
+    Bmc,
+    BuildMetadata,
+    KeyvalueMetadata,
+    UserMetadata,
+    DnaCertificate,
+    Pdi,
+    BitstreamPartialPdi,
+    PartitionMetadata,
+    EmulationData,
+    SystemMetadata,
+    SoftKernel,
+    AskFlash,
+    AieMetadata,
+    AskGroupTopology,
+    AskGroupConnectivity,
+    /// A section kind not known to this crate.
+    Other(u32),
+}
+
+impl From<u32> for SectionKind {
+    fn from(kind: u32) -> Self {
+        match kind {
+            0 => Self::Bitstream,
+            1 => Self::ClearingBitstream,
+            2 => Self::EmbeddedMetadata,
+            3 => Self::Firmware,
+            4 => Self::DebugData,
+            5 => Self::SchedFirmware,
+            6 => Self::MemTopology,
+            7 => Self::Connectivity,
+            8 => Self::IpLayout,
+            9 => Self::DebugIpLayout,
+            10 => Self::DesignCheckPoint,
+            11 => Self::ClockFreqTopology,
+            12 => Self::Mcs,
+            13 => Self::Bmc,
+            14 => Self::BuildMetadata,
+            15 => Self::KeyvalueMetadata,
+            16 => Self::UserMetadata,
+            17 => Self::DnaCertificate,
+            18 => Self::Pdi,
+            19 => Self::BitstreamPartialPdi,
+            20 => Self::PartitionMetadata,
+            21 => Self::EmulationData,
+            22 => Self::SystemMetadata,
+            23 => Self::SoftKernel,
+            24 => Self::AskFlash,
+            25 => Self::AieMetadata,
+            26 => Self::AskGroupTopology,
+            27 => Self::AskGroupConnectivity,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Entry of the section table of an xclbin (`axlf_section_header`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SectionHeader {
+    pub kind: SectionKind,
+    pub name: String,
+    /// Offset of the section in the xclbin, in bytes.
+    pub offset: u64,
+    /// Size of the section in bytes.
+    pub size: u64,
+}
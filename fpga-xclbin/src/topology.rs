@@ -0,0 +1,251 @@
+use crate::{reader::Reader, Result};
+
+/// Type of a memory bank (`MEM_TYPE`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MemType {
+    Ddr3,
+    Ddr4,
+    Dram,
+    Streaming,
+    PreallocatedGlobal,
+    Are,
+    Hbm,
+    Bram,
+    Uram,
+    StreamingConnection,
+    Host,
+    PsKernel,
+    /// A memory type not known to this crate.
+    Other(u8),
+}
+
+impl From<u8> for MemType {
+    fn from(mem_type: u8) -> Self {
+        match mem_type {
+            0 => Self::Ddr3,
+            1 => Self::Ddr4,
+            2 => Self::Dram,
+            3 => Self::Streaming,
+            4 => Self::PreallocatedGlobal,
+            5 => Self::Are,
+            6 => Self::Hbm,
+            7 => Self::Bram,
+            8 => Self::Uram,
+            9 => Self::StreamingConnection,
+            10 => Self::Host,
+            11 => Self::PsKernel,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A memory bank of the MEM_TOPOLOGY section (`mem_data`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemData {
+    pub mem_type: MemType,
+    pub used: bool,
+    /// Size in bytes, or route id for streaming memories.
+    pub size: u64,
+    /// Base address, or flow id for streaming memories.
+    pub base_address: u64,
+    pub tag: String,
+}
+
+/// A connection of a kernel argument to a memory bank, from the CONNECTIVITY
+/// section (`connection`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Connection {
+    pub arg_index: i32,
+    /// Index in the IP_LAYOUT section.
+    pub ip_layout_index: i32,
+    /// Index in the MEM_TOPOLOGY section.
+    pub mem_data_index: i32,
+}
+
+/// Type of an IP (`IP_TYPE`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IpType {
+    Mb,
+    Kernel,
+    Dnasc,
+    Ddr4Controller,
+    MemDdr4,
+    MemHbm,
+    MemHbmEcc,
+    PsKernel,
+    /// An IP type not known to this crate.
+    Other(u32),
+}
+
+impl From<u32> for IpType {
+    fn from(ip_type: u32) -> Self {
+        match ip_type {
+            0 => Self::Mb,
+            1 => Self::Kernel,
+            2 => Self::Dnasc,
+            3 => Self::Ddr4Controller,
+            4 => Self::MemDdr4,
+            5 => Self::MemHbm,
+            6 => Self::MemHbmEcc,
+            7 => Self::PsKernel,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// Block-level control protocol of a kernel IP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ControlProtocol {
+    ApCtrlHs,
+    ApCtrlChain,
+    ApCtrlNone,
+    ApCtrlMe,
+    AccelAdapter,
+    FastAdapter,
+    /// A control protocol not known to this crate.
+    Other(u8),
+}
+
+impl From<u8> for ControlProtocol {
+    fn from(protocol: u8) -> Self {
+        match protocol {
+            0 => Self::ApCtrlHs,
+            1 => Self::ApCtrlChain,
+            2 => Self::ApCtrlNone,
+            3 => Self::ApCtrlMe,
+            4 => Self::AccelAdapter,
+            5 => Self::FastAdapter,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// An IP of the IP_LAYOUT section (`ip_data`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpData {
+    pub ip_type: IpType,
+    /// Type specific properties, e.g. the interrupt enable and control
+    /// protocol of kernels, or the memory index of memory controllers.
+    pub properties: u32,
+    pub base_address: u64,
+    pub name: String,
+}
+
+impl IpData {
+    /// Returns true if the interrupt of this kernel is enabled.
+    pub fn interrupt_enabled(&self) -> bool {
+        self.ip_type == IpType::Kernel && self.properties & 0x1 != 0
+    }
+
+    /// Returns the control protocol of this IP, if it is a kernel.
+    pub fn control_protocol(&self) -> Option<ControlProtocol> {
+        if self.ip_type == IpType::Kernel {
+            Some(ControlProtocol::from(
+                ((self.properties & 0xff00) >> 8) as u8,
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Type of a clock (`CLOCK_TYPE`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ClockType {
+    Unused,
+    Data,
+    Kernel,
+    System,
+    /// A clock type not known to this crate.
+    Other(u8),
+}
+
+impl From<u8> for ClockType {
+    fn from(clock_type: u8) -> Self {
+        match clock_type {
+            0 => Self::Unused,
+            1 => Self::Data,
+            2 => Self::Kernel,
+            3 => Self::System,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A clock of the CLOCK_FREQ_TOPOLOGY section (`clock_freq`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClockFreq {
+    pub frequency_mhz: u16,
+    pub clock_type: ClockType,
+    pub name: String,
+}
+
+/// Parses the `m_count` entries of `size` bytes starting at `offset` of a
+/// section.
+fn parse_entries<T, F>(
+    reader: Reader,
+    count: i64,
+    offset: usize,
+    size: usize,
+    parse: F,
+) -> Result<Vec<T>>
+where
+    F: Fn(Reader, usize) -> Result<T>,
+{
+    let count = reader.entries(count, offset, size)?;
+    (0..count)
+        .map(|index| parse(reader, offset + index * size))
+        .collect()
+}
+
+pub(crate) fn mem_topology(data: &[u8]) -> Result<Vec<MemData>> {
+    let reader = Reader::new(data);
+    let count = reader.i32(0)?;
+    parse_entries(reader, count.into(), 8, 40, |reader, offset| {
+        Ok(MemData {
+            mem_type: reader.u8(offset)?.into(),
+            used: reader.u8(offset + 1)? != 0,
+            // The size is stored in KiB.
+            size: reader.u64(offset + 8)?.saturating_mul(1024),
+            base_address: reader.u64(offset + 16)?,
+            tag: reader.str(offset + 24, 16)?,
+        })
+    })
+}
+
+pub(crate) fn connectivity(data: &[u8]) -> Result<Vec<Connection>> {
+    let reader = Reader::new(data);
+    let count = reader.i32(0)?;
+    parse_entries(reader, count.into(), 4, 12, |reader, offset| {
+        Ok(Connection {
+            arg_index: reader.i32(offset)?,
+            ip_layout_index: reader.i32(offset + 4)?,
+            mem_data_index: reader.i32(offset + 8)?,
+        })
+    })
+}
+
+pub(crate) fn ip_layout(data: &[u8]) -> Result<Vec<IpData>> {
+    let reader = Reader::new(data);
+    let count = reader.i32(0)?;
+    parse_entries(reader, count.into(), 8, 80, |reader, offset| {
+        Ok(IpData {
+            ip_type: reader.u32(offset)?.into(),
+            properties: reader.u32(offset + 4)?,
+            base_address: reader.u64(offset + 8)?,
+            name: reader.str(offset + 16, 64)?,
+        })
+    })
+}
+
+pub(crate) fn clock_freq_topology(data: &[u8]) -> Result<Vec<ClockFreq>> {
+    let reader = Reader::new(data);
+    let count = reader.i16(0)?;
+    parse_entries(reader, count.into(), 2, 136, |reader, offset| {
+        Ok(ClockFreq {
+            frequency_mhz: reader.u16(offset)?,
+            clock_type: reader.u8(offset + 2)?.into(),
+            name: reader.str(offset + 8, 128)?,
+        })
+    })
+}
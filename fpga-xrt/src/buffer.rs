@@ -0,0 +1,155 @@
+use crate::{ffi, Result, Xrt};
+use cxx::UniquePtr;
+use std::{fmt::Debug, marker::PhantomData};
+
+/// Kind of memory backing a buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BufferKind {
+    /// Host memory synchronized with device memory.
+    Normal,
+    /// Cacheable host memory synchronized with device memory.
+    Cacheable,
+    /// Device memory only, which can't be mapped.
+    DeviceOnly,
+    /// Host memory only, accessed by the device directly.
+    HostOnly,
+    /// Device memory exposed on the PCIe BAR for peer-to-peer transfers.
+    P2p,
+    /// Shared virtual memory.
+    Svm,
+}
+
+impl From<BufferKind> for ffi::bo_flags {
+    fn from(kind: BufferKind) -> Self {
+        match kind {
+            BufferKind::Normal => ffi::bo_flags::normal,
+            BufferKind::Cacheable => ffi::bo_flags::cacheable,
+            BufferKind::DeviceOnly => ffi::bo_flags::device_only,
+            BufferKind::HostOnly => ffi::bo_flags::host_only,
+            BufferKind::P2p => ffi::bo_flags::p2p,
+            BufferKind::Svm => ffi::bo_flags::svm,
+        }
+    }
+}
+
+/// Direction of a buffer synchronization.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncDirection {
+    /// Copy the host memory of the buffer to the device.
+    ToDevice,
+    /// Copy the device memory of the buffer to the host.
+    FromDevice,
+}
+
+/// Handle of an exported buffer, which can be imported by another device or
+/// process for peer-to-peer transfers.
+pub type ExportHandle = i32;
+
+/// A buffer object in a memory bank of a device.
+///
+/// A buffer can't outlive the device it was allocated on.
+pub struct Buffer<'device> {
+    pub(crate) bo: UniquePtr<ffi::Bo>,
+    _device: PhantomData<&'device Xrt>,
+}
+
+impl<'device> Buffer<'device> {
+    fn new(bo: UniquePtr<ffi::Bo>) -> Self {
+        Self {
+            bo,
+            _device: PhantomData,
+        }
+    }
+
+    /// Returns the size of this buffer in bytes.
+    pub fn size(&self) -> usize {
+        self.bo.size()
+    }
+
+    /// Returns the device address of this buffer.
+    pub fn address(&self) -> u64 {
+        self.bo.address()
+    }
+
+    /// Maps this buffer into host memory.
+    pub fn map(&mut self) -> Result<&mut [u8]> {
+        Ok(self.bo.pin_mut().map()?)
+    }
+
+    /// Synchronizes `len` bytes at `offset` between host and device memory.
+    pub fn sync(&mut self, direction: SyncDirection, offset: usize, len: usize) -> Result<()> {
+        match direction {
+            SyncDirection::ToDevice => self.bo.pin_mut().sync_to_device(len, offset)?,
+            SyncDirection::FromDevice => self.bo.pin_mut().sync_from_device(len, offset)?,
+        }
+        Ok(())
+    }
+
+    /// Synchronizes the entire buffer between host and device memory.
+    pub fn sync_all(&mut self, direction: SyncDirection) -> Result<()> {
+        self.sync(direction, 0, self.size())
+    }
+
+    /// Copies `data` into the host memory of this buffer at `offset`.
+    pub fn write(&mut self, data: &[u8], offset: usize) -> Result<()> {
+        Ok(self.bo.pin_mut().write_bytes(data, offset)?)
+    }
+
+    /// Copies the host memory of this buffer at `offset` into `data`.
+    pub fn read(&mut self, data: &mut [u8], offset: usize) -> Result<()> {
+        Ok(self.bo.pin_mut().read_bytes(data, offset)?)
+    }
+
+    /// Returns a buffer of `size` bytes at `offset` in this buffer.
+    ///
+    /// The sub-buffer shares memory with this buffer, which is therefore
+    /// borrowed mutably for as long as the sub-buffer exists:
+    ///
+    /// ```compile_fail
+    /// # use fpga_xrt::{BufferKind, Xrt};
+    /// # fn main() -> fpga_xrt::Result<()> {
+    /// # let xrt = Xrt::from_device_index(0)?;
+    /// let mut buffer = xrt.buffer(4096, BufferKind::Normal, 0)?;
+    /// let mut sub_buffer = buffer.sub_buffer(1024, 0)?;
+    /// let parent = buffer.map()?;
+    /// let child = sub_buffer.map()?;
+    /// parent[0] = child[0];
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sub_buffer(&mut self, size: usize, offset: usize) -> Result<Buffer<'_>> {
+        Ok(Self::new(ffi::new_sub_bo(&self.bo, size, offset)?))
+    }
+
+    /// Exports this buffer for import by another device.
+    pub fn export(&mut self) -> Result<ExportHandle> {
+        Ok(self.bo.pin_mut().export_handle()?)
+    }
+}
+
+impl Debug for Buffer<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buffer")
+            .field("size", &self.size())
+            .field("address", &self.address())
+            .finish()
+    }
+}
+
+impl Xrt {
+    /// Allocates a buffer of `size` bytes in memory bank `group`, which is
+    /// typically obtained from [crate::Kernel::group_id].
+    pub fn buffer(&self, size: usize, kind: BufferKind, group: u32) -> Result<Buffer<'_>> {
+        Ok(Buffer::new(ffi::new_bo(
+            &self.device,
+            size,
+            kind.into(),
+            group,
+        )?))
+    }
+
+    /// Imports a buffer exported by another device.
+    pub fn import_buffer(&self, handle: ExportHandle) -> Result<Buffer<'_>> {
+        Ok(Buffer::new(ffi::new_bo_import(&self.device, handle)?))
+    }
+}
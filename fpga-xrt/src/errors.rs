@@ -0,0 +1,228 @@
+use crate::ffi;
+use cxx::Exception;
+use fpga_core::{Context, MmioError};
+
+/// Errors of the XRT platform.
+///
+/// Errors raised by XRT keep the original exception, which carries the
+/// message of XRT.
+#[derive(Debug)]
+pub enum Error {
+    /// Exception thrown by XRT, not covered by the other variants, with the
+    /// errno code of the system error that caused it, if any.
+    Exception {
+        source: Exception,
+        code: Option<i32>,
+    },
+    /// The device could not be opened.
+    DeviceOpen(Exception),
+    /// The data is not a valid xclbin.
+    InvalidXclbin(Exception),
+    /// The xclbin has no kernel or IP with this name, or none of the selected
+    /// compute units are available.
+    KernelNotFound { name: String, source: Exception },
+    /// Insufficient permissions to access the device, e.g. to load an xclbin.
+    PermissionDenied(Exception),
+    /// Invalid access to the registers of a kernel or IP.
+    Mmio(MmioError),
+    /// I/O error, e.g. when reading a file.
+    Io(std::io::Error),
+    /// A report of XRT could not be parsed.
+    Json(serde_json::Error),
+    /// The kernel has no argument with this name.
+    UnknownArgument(String),
+    /// The xclbin metadata of this kernel is not available.
+    MissingMetadata(String),
+    /// The device does not report the sensor with this location.
+    SensorNotFound(String),
+    /// The run can't be started, as its previous start did not finish yet.
+    RunInFlight,
+    /// No device matches the configuration.
+    NoMatchingDevice {
+        /// The PCIe address and shell name of the available devices.
+        available: Vec<String>,
+    },
+    /// The xclbin targets a different shell than the one of the device.
+    IncompatibleXclbin {
+        /// Interface UUID or name of the shell of the device.
+        device: String,
+        /// Interface UUID or name of the shell targeted by the xclbin.
+        xclbin: String,
+    },
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Exception {
+                source: exception, ..
+            }
+            | Self::DeviceOpen(exception)
+            | Self::InvalidXclbin(exception)
+            | Self::KernelNotFound {
+                source: exception, ..
+            }
+            | Self::PermissionDenied(exception) => Some(exception),
+            Self::Mmio(error) => Some(error),
+            Self::Io(error) => Some(error),
+            Self::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exception {
+                source: exception, ..
+            }
+            | Self::PermissionDenied(exception) => {
+                write!(f, "{}", exception.what())
+            }
+            Self::DeviceOpen(exception) => {
+                write!(f, "failed to open device: {}", exception.what())
+            }
+            Self::InvalidXclbin(exception) => write!(f, "invalid xclbin: {}", exception.what()),
+            Self::KernelNotFound { name, source } => {
+                write!(f, "kernel {} not found: {}", name, source.what())
+            }
+            Self::Mmio(error) => write!(f, "{}", error),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Json(error) => write!(f, "invalid report: {}", error),
+            Self::UnknownArgument(name) => write!(f, "unknown kernel argument: {}", name),
+            Self::MissingMetadata(name) => write!(f, "no xclbin metadata of kernel {}", name),
+            Self::SensorNotFound(location) => write!(f, "sensor {} not found", location),
+            Self::RunInFlight => write!(f, "run is still in flight"),
+            Self::NoMatchingDevice { available } if available.is_empty() => {
+                write!(f, "no matching device, no devices available")
+            }
+            Self::NoMatchingDevice { available } => write!(
+                f,
+                "no matching device, available devices: {}",
+                available.join(", ")
+            ),
+            Self::IncompatibleXclbin { device, xclbin } => write!(
+                f,
+                "xclbin targets shell {}, but device has shell {}",
+                xclbin, device
+            ),
+        }
+    }
+}
+
+/// Result type with `Error` error type.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Returns the errno code of the system error that caused the last exception
+/// thrown by XRT on this thread, if any. Must be called before any other call
+/// into XRT after the exception, i.e. when converting it.
+fn error_code() -> Option<i32> {
+    match ffi::last_error_code() {
+        0 => None,
+        code => Some(code),
+    }
+}
+
+fn is_permission_denied(code: Option<i32>) -> bool {
+    matches!(code, Some(libc::EACCES) | Some(libc::EPERM))
+}
+
+fn is_not_found(code: Option<i32>) -> bool {
+    matches!(
+        code,
+        Some(libc::ENOENT) | Some(libc::ENODEV) | Some(libc::ENXIO)
+    )
+}
+
+impl Error {
+    /// Returns the error of a failure to open a device.
+    pub(crate) fn device_open(exception: Exception) -> Self {
+        match error_code() {
+            code if is_permission_denied(code) => Self::PermissionDenied(exception),
+            code @ Some(_) if !is_not_found(code) => Self::Exception {
+                source: exception,
+                code,
+            },
+            _ => Self::DeviceOpen(exception),
+        }
+    }
+
+    /// Returns the error of a failure to open kernel or IP `name`.
+    pub(crate) fn kernel(name: &str, exception: Exception) -> Self {
+        match error_code() {
+            code if is_permission_denied(code) => Self::PermissionDenied(exception),
+            code @ Some(_) if !is_not_found(code) => Self::Exception {
+                source: exception,
+                code,
+            },
+            _ => Self::KernelNotFound {
+                name: name.to_string(),
+                source: exception,
+            },
+        }
+    }
+}
+
+impl From<Exception> for Error {
+    fn from(exception: Exception) -> Self {
+        match error_code() {
+            code if is_permission_denied(code) => Self::PermissionDenied(exception),
+            code => Self::Exception {
+                source: exception,
+                code,
+            },
+        }
+    }
+}
+
+impl From<MmioError> for Error {
+    fn from(error: MmioError) -> Self {
+        Self::Mmio(error)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+impl From<Error> for fpga_core::Error {
+    fn from(error: Error) -> Self {
+        let kind: fn(Context) -> Self = match &error {
+            Error::DeviceOpen(_)
+            | Error::KernelNotFound { .. }
+            | Error::NoMatchingDevice { .. } => Self::NotFound,
+            Error::PermissionDenied(_) => Self::PermissionDenied,
+            Error::RunInFlight => Self::Busy,
+            Error::InvalidXclbin(_)
+            | Error::IncompatibleXclbin { .. }
+            | Error::UnknownArgument(_)
+            | Error::Mmio(_) => Self::InvalidParam,
+            Error::Io(io) => match io.kind() {
+                std::io::ErrorKind::NotFound => Self::NotFound,
+                std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+                _ => Self::Backend,
+            },
+            Error::SensorNotFound(_) | Error::MissingMetadata(_) => Self::Unsupported,
+            Error::Json(_) => Self::Backend,
+            Error::Exception { code, .. } => match code {
+                Some(libc::ENOENT) | Some(libc::ENODEV) | Some(libc::ENXIO) => Self::NotFound,
+                Some(libc::EBUSY) | Some(libc::EAGAIN) => Self::Busy,
+                Some(libc::ETIMEDOUT) | Some(libc::ETIME) => Self::Timeout,
+                Some(libc::ENOSYS) | Some(libc::EOPNOTSUPP) => Self::Unsupported,
+                Some(libc::EINVAL) | Some(libc::ERANGE) => Self::InvalidParam,
+                Some(libc::EACCES) | Some(libc::EPERM) => Self::PermissionDenied,
+                _ => Self::Backend,
+            },
+        };
+        kind(Context::new().with_source(error))
+    }
+}
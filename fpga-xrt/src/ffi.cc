@@ -1,16 +1,33 @@
 #include "src/ffi.h"
 // #include "fpga-xrt/src/ffi.rs.h"
 
+#include <chrono>
 #include <iostream>
+#include <stdexcept>
 
 // Xclbin
 namespace xrt
 {
-  rust::Str XclbinArg::name() const
+  rust::String XclbinMem::tag() const
+  {
+    return get_tag();
+  }
+
+  rust::String XclbinArg::name() const
   {
     return get_name();
   }
 
+  rust::String XclbinArg::host_type() const
+  {
+    return get_host_type();
+  }
+
+  rust::String XclbinArg::port() const
+  {
+    return get_port();
+  }
+
   std::unique_ptr<std::vector<XclbinMem>> XclbinArg::mems() const
   {
     std::vector<xclbin::mem> input = get_mems();
@@ -18,12 +35,26 @@ namespace xrt
     return std::make_unique<std::vector<XclbinMem>>(output);
   }
 
-  rust::Str XclbinKernel::name() const
+  rust::String XclbinKernel::name() const
   {
     return get_name();
   }
 
-  rust::Str XclbinIp::name() const
+  std::unique_ptr<std::vector<XclbinIp>> XclbinKernel::cus() const
+  {
+    std::vector<xclbin::ip> input = get_cus();
+    std::vector<XclbinIp> output(input.begin(), input.end());
+    return std::make_unique<std::vector<XclbinIp>>(output);
+  }
+
+  std::unique_ptr<std::vector<XclbinArg>> XclbinKernel::args() const
+  {
+    std::vector<xclbin::arg> input = get_args();
+    std::vector<XclbinArg> output(input.begin(), input.end());
+    return std::make_unique<std::vector<XclbinArg>>(output);
+  }
+
+  rust::String XclbinIp::name() const
   {
     return get_name();
   }
@@ -41,7 +72,7 @@ namespace xrt
     return std::make_unique<XclbinArg>(arg);
   }
 
-  std::unique_ptr<Xclbin> new_xclbin(const rust::Slice<const int8_t> data)
+  std::unique_ptr<Xclbin> new_xclbin(const rust::Slice<const uint8_t> data)
   {
     std::vector<char> input(data.begin(), data.end());
     return std::make_unique<Xclbin>(input);
@@ -71,11 +102,18 @@ namespace xrt
     return std::make_unique<XclbinIp>(get_ip(std::string(name)));
   }
 
-  rust::Str Xclbin::xsa_name() const
+  rust::String Xclbin::xsa_name() const
   {
     return get_xsa_name();
   }
 
+  std::array<unsigned char, 16> Xclbin::interface_uuid() const
+  {
+    std::array<unsigned char, 16> array;
+    memcpy(array.data(), get_interface_uuid().get(), sizeof(unsigned char) * 16);
+    return array;
+  }
+
   std::array<unsigned char, 16> Xclbin::uuid() const
   {
     std::array<unsigned char, 16> array;
@@ -98,7 +136,7 @@ namespace xrt
     return std::make_unique<Device>(std::string(bdf));
   }
 
-  rust::Str Device::bdf() const
+  rust::String Device::bdf() const
   {
     return get_info<xrt::info::device::bdf>();
   }
@@ -125,7 +163,7 @@ namespace xrt
     return get_info<xrt::info::device::m2m>();
   }
 
-  rust::Str Device::name() const
+  rust::String Device::name() const
   {
     return get_info<xrt::info::device::name>();
   }
@@ -140,45 +178,45 @@ namespace xrt
     return get_info<xrt::info::device::offline>();
   }
 
-  // rust::String Device::electrical() const
-  // {
-  //   return get_info<xrt::info::device::electrical>();
-  // }
+  rust::String Device::electrical() const
+  {
+    return get_info<xrt::info::device::electrical>();
+  }
 
-  // rust::String Device::thermal() const
-  // {
-  //   return get_info<xrt::info::device::thermal>();
-  // }
+  rust::String Device::thermal() const
+  {
+    return get_info<xrt::info::device::thermal>();
+  }
 
-  // rust::String Device::mechanical() const
-  // {
-  //   return get_info<xrt::info::device::mechanical>();
-  // }
+  rust::String Device::mechanical() const
+  {
+    return get_info<xrt::info::device::mechanical>();
+  }
 
-  // rust::String Device::memory() const
-  // {
-  //   return get_info<xrt::info::device::memory>();
-  // }
+  rust::String Device::memory() const
+  {
+    return get_info<xrt::info::device::memory>();
+  }
 
-  // rust::String Device::platform() const
-  // {
-  //   return get_info<xrt::info::device::platform>();
-  // }
+  rust::String Device::platform() const
+  {
+    return get_info<xrt::info::device::platform>();
+  }
 
-  // rust::String Device::pcie_info() const
-  // {
-  //   return get_info<xrt::info::device::pcie_info>();
-  // }
+  rust::String Device::pcie_info() const
+  {
+    return get_info<xrt::info::device::pcie_info>();
+  }
 
-  // rust::String Device::host() const
-  // {
-  //   return get_info<xrt::info::device::host>();
-  // }
+  rust::String Device::host() const
+  {
+    return get_info<xrt::info::device::host>();
+  }
 
-  // rust::String Device::dynamic_regions() const
-  // {
-  //   return get_info<xrt::info::device::dynamic_regions>();
-  // }
+  rust::String Device::dynamic_regions() const
+  {
+    return get_info<xrt::info::device::dynamic_regions>();
+  }
 
   std::array<unsigned char, 16> Device::xclbin_uuid() const
   {
@@ -213,6 +251,124 @@ namespace xrt
   }
 }
 
+// Buffer
+namespace xrt
+{
+  std::unique_ptr<Bo> new_bo(const Device &device, size_t size, bo_flags flags, uint32_t group)
+  {
+    return std::make_unique<Bo>(device, size, flags, group);
+  }
+
+  std::unique_ptr<Bo> new_bo_import(const Device &device, int32_t handle)
+  {
+    return std::make_unique<Bo>(static_cast<xclDeviceHandle>(device), handle);
+  }
+
+  std::unique_ptr<Bo> new_sub_bo(const Bo &parent, size_t size, size_t offset)
+  {
+    return std::make_unique<Bo>(parent, size, offset);
+  }
+
+  rust::Slice<uint8_t> Bo::map()
+  {
+    return rust::Slice<uint8_t>(xrt::bo::map<uint8_t *>(), size());
+  }
+
+  void Bo::sync_to_device(size_t size, size_t offset)
+  {
+    sync(XCL_BO_SYNC_BO_TO_DEVICE, size, offset);
+  }
+
+  void Bo::sync_from_device(size_t size, size_t offset)
+  {
+    sync(XCL_BO_SYNC_BO_FROM_DEVICE, size, offset);
+  }
+
+  void Bo::write_bytes(rust::Slice<const uint8_t> data, size_t offset)
+  {
+    write(data.data(), data.size(), offset);
+  }
+
+  void Bo::read_bytes(rust::Slice<uint8_t> data, size_t offset)
+  {
+    read(data.data(), data.size(), offset);
+  }
+
+  int32_t Bo::export_handle()
+  {
+    return export_buffer();
+  }
+}
+
+// Run
+namespace xrt
+{
+  std::unique_ptr<Run> new_run(const kernel &kernel)
+  {
+    return std::make_unique<Run>(kernel);
+  }
+
+  void Run::set_arg_scalar(int32_t index, rust::Slice<const uint8_t> bytes)
+  {
+    // xrt::run copies sizeof(value) bytes of scalar arguments, so any type of
+    // the same size can be used to pass the raw bytes.
+    switch (bytes.size())
+    {
+    case 1:
+    {
+      uint8_t value;
+      memcpy(&value, bytes.data(), 1);
+      return set_arg(index, value);
+    }
+    case 2:
+    {
+      uint16_t value;
+      memcpy(&value, bytes.data(), 2);
+      return set_arg(index, value);
+    }
+    case 4:
+    {
+      uint32_t value;
+      memcpy(&value, bytes.data(), 4);
+      return set_arg(index, value);
+    }
+    case 8:
+    {
+      uint64_t value;
+      memcpy(&value, bytes.data(), 8);
+      return set_arg(index, value);
+    }
+    default:
+      throw std::invalid_argument("invalid size of scalar argument");
+    }
+  }
+
+  void Run::set_arg_bo(int32_t index, const Bo &bo)
+  {
+    set_arg(index, static_cast<const xrt::bo &>(bo));
+  }
+
+  uint32_t Run::wait_for(uint32_t timeout_ms) const
+  {
+    return wait(std::chrono::milliseconds(timeout_ms));
+  }
+
+  uint32_t Run::cmd_state() const
+  {
+    return state();
+  }
+
+  void Run::on_complete(rust::Fn<void(size_t, uint32_t)> callback, size_t data)
+  {
+    // Invoked from an XRT thread every time the run completes.
+    add_callback(
+        ERT_CMD_STATE_COMPLETED,
+        [callback, data](const void *, ert_cmd_state state, void *)
+        { callback(data, state); },
+        nullptr);
+  }
+}
+
 // IP
 namespace xrt
 {
@@ -232,4 +388,23 @@ namespace xrt
   {
     xrt::ini::set(std::string(key), std::string(value));
   }
-}
\ No newline at end of file
+}
+
+// Errors
+namespace xrt
+{
+  namespace
+  {
+    thread_local int32_t error_code = 0;
+  }
+
+  void set_error_code(int32_t code)
+  {
+    error_code = code;
+  }
+
+  int32_t last_error_code()
+  {
+    return error_code;
+  }
+}
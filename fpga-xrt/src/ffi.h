@@ -1,6 +1,7 @@
 #pragma once
 #include "rust/cxx.h"
 
+#include "fpga-xrt/xrt/src/runtime_src/core/include/xrt/xrt_bo.h"
 #include "fpga-xrt/xrt/src/runtime_src/core/include/xrt/xrt_device.h"
 #include "fpga-xrt/xrt/src/runtime_src/core/include/xrt/xrt_kernel.h"
 
@@ -8,6 +9,9 @@
 #include "fpga-xrt/xrt/src/runtime_src/core/include/experimental/xrt_ip.h"
 #include "fpga-xrt/xrt/src/runtime_src/core/include/experimental/xrt_xclbin.h"
 
+#include <cstdlib>
+#include <system_error>
+
 namespace xrt
 {
   class XclbinMem : public xclbin::mem
@@ -16,6 +20,7 @@ namespace xrt
 
   public:
     XclbinMem(const xclbin::mem &mem) : xclbin::mem(mem){};
+    rust::String tag() const;
   };
 
   class XclbinArg : public xclbin::arg
@@ -24,28 +29,32 @@ namespace xrt
 
   public:
     XclbinArg(const xclbin::arg &arg) : xclbin::arg(arg){};
-    rust::Str name() const;
+    rust::String name() const;
     std::unique_ptr<std::vector<XclbinMem>> mems() const;
+    rust::String host_type() const;
+    rust::String port() const;
   };
 
-  class XclbinKernel : public xclbin::kernel
+  class XclbinIp : public xclbin::ip
   {
-    using xclbin::kernel::kernel;
+    using xclbin::ip::ip;
 
   public:
-    XclbinKernel(const xclbin::kernel &kernel) : xclbin::kernel(kernel){};
-    rust::Str name() const;
+    XclbinIp(const xclbin::ip &ip) : xclbin::ip(ip){};
+    rust::String name() const;
+    std::unique_ptr<std::vector<XclbinArg>> args() const;
+    std::unique_ptr<XclbinArg> arg(int32_t index) const;
   };
 
-  class XclbinIp : public xclbin::ip
+  class XclbinKernel : public xclbin::kernel
   {
-    using xclbin::ip::ip;
+    using xclbin::kernel::kernel;
 
   public:
-    XclbinIp(const xclbin::ip &ip) : xclbin::ip(ip){};
-    rust::Str name() const;
+    XclbinKernel(const xclbin::kernel &kernel) : xclbin::kernel(kernel){};
+    rust::String name() const;
     std::unique_ptr<std::vector<XclbinArg>> args() const;
-    std::unique_ptr<XclbinArg> arg(int32_t index) const;
+    std::unique_ptr<std::vector<XclbinIp>> cus() const;
   };
 
   class Xclbin : public xrt::xclbin
@@ -53,16 +62,17 @@ namespace xrt
     using xrt::xclbin::xclbin;
 
   public:
-    rust::Str xsa_name() const;
+    rust::String xsa_name() const;
     std::unique_ptr<std::vector<XclbinKernel>> kernels() const;
     std::unique_ptr<XclbinKernel> kernel(const rust::Str name) const;
     std::unique_ptr<std::vector<XclbinIp>> ips() const;
     std::unique_ptr<XclbinIp> ip(const rust::Str name) const;
     std::array<unsigned char, 16> uuid() const;
+    std::array<unsigned char, 16> interface_uuid() const;
   };
 
   std::unique_ptr<Xclbin>
-  new_xclbin(const rust::Slice<const int8_t> data);
+  new_xclbin(const rust::Slice<const uint8_t> data);
 }
 
 namespace xrt
@@ -73,22 +83,22 @@ namespace xrt
 
   public:
     // get_info methods.
-    rust::Str bdf() const;
+    rust::String bdf() const;
     std::array<unsigned char, 16> interface_uuid() const;
     uint32_t kdma() const;
     unsigned long max_clock_frequency_mhz() const;
     bool m2m() const;
-    rust::Str name() const;
+    rust::String name() const;
     bool nodma() const;
     bool offline() const;
-    // rust::String electrical() const;
-    // rust::String thermal() const;
-    // rust::String mechanical() const;
-    // rust::String memory() const;
-    // rust::String platform() const;
-    // rust::String pcie_info() const;
-    // rust::String host() const;
-    // rust::String dynamic_regions() const;
+    rust::String electrical() const;
+    rust::String thermal() const;
+    rust::String mechanical() const;
+    rust::String memory() const;
+    rust::String platform() const;
+    rust::String pcie_info() const;
+    rust::String host() const;
+    rust::String dynamic_regions() const;
 
     // Modified member functions.
     std::array<unsigned char, 16> xclbin_uuid() const;
@@ -120,6 +130,50 @@ namespace xrt
       kernel_cu_access_mode mode);
 }
 
+// Buffer
+namespace xrt
+{
+  // export nested enum class.
+  using bo_flags = xrt::bo::flags;
+
+  class Bo : public xrt::bo
+  {
+    using xrt::bo::bo;
+
+  public:
+    rust::Slice<uint8_t> map();
+    void sync_to_device(size_t size, size_t offset);
+    void sync_from_device(size_t size, size_t offset);
+    void write_bytes(rust::Slice<const uint8_t> data, size_t offset);
+    void read_bytes(rust::Slice<uint8_t> data, size_t offset);
+    int32_t export_handle();
+  };
+
+  // FFI constructors.
+  std::unique_ptr<Bo> new_bo(const Device &device, size_t size, bo_flags flags, uint32_t group);
+  std::unique_ptr<Bo> new_bo_import(const Device &device, int32_t handle);
+  std::unique_ptr<Bo> new_sub_bo(const Bo &parent, size_t size, size_t offset);
+}
+
+// Run
+namespace xrt
+{
+  class Run : public xrt::run
+  {
+    using xrt::run::run;
+
+  public:
+    void set_arg_scalar(int32_t index, rust::Slice<const uint8_t> bytes);
+    void set_arg_bo(int32_t index, const Bo &bo);
+    uint32_t wait_for(uint32_t timeout_ms) const;
+    uint32_t cmd_state() const;
+    void on_complete(rust::Fn<void(size_t, uint32_t)> callback, size_t data);
+  };
+
+  // FFI constructor.
+  std::unique_ptr<Run> new_run(const kernel &kernel);
+}
+
 // IP
 namespace xrt
 {
@@ -135,3 +189,42 @@ namespace xrt
 {
   void set_ini(rust::Str key, rust::Str value);
 }
+
+// Errors
+namespace xrt
+{
+  // Error code of the system error that caused the last exception thrown
+  // to Rust on this thread, or 0 if it was not caused by a system error.
+  void set_error_code(int32_t code);
+  int32_t last_error_code();
+}
+
+namespace rust
+{
+  namespace behavior
+  {
+    // Converts exceptions into Rust errors. XRT reports errors as system
+    // errors with an errno code, which is recorded before the exception is
+    // converted, as the Rust error only keeps the message.
+    template <typename Try, typename Fail>
+    static void trycatch(Try &&func, Fail &&fail) noexcept
+    try
+    {
+      func();
+    }
+    catch (const std::system_error &e)
+    {
+      const auto &category = e.code().category();
+      xrt::set_error_code(
+          category == std::system_category() || category == std::generic_category()
+              ? std::abs(e.code().value())
+              : 0);
+      fail(e.what());
+    }
+    catch (const std::exception &e)
+    {
+      xrt::set_error_code(0);
+      fail(e.what());
+    }
+  }
+}
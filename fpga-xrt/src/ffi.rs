@@ -12,25 +12,25 @@ mod ffi {
 
         // Modified members
         fn xclbin_uuid(self: &Device) -> [u8; 16];
-        fn load(self: Pin<&mut Device>, xclbin: &Xclbin) -> [u8; 16];
+        fn load(self: Pin<&mut Device>, xclbin: &Xclbin) -> Result<[u8; 16]>;
 
         // Custom members
-        fn bdf(self: &Device) -> &str;
+        fn bdf(self: &Device) -> String;
         fn interface_uuid(self: &Device) -> [u8; 16];
         fn kdma(self: &Device) -> u32;
         fn max_clock_frequency_mhz(self: &Device) -> u64;
         fn m2m(self: &Device) -> bool;
-        fn name(self: &Device) -> &str;
+        fn name(self: &Device) -> String;
         fn nodma(self: &Device) -> bool;
         fn offline(self: &Device) -> bool;
-        // fn electrical(self: &Device) -> String;
-        // fn thermal(self: &Device) -> String;
-        // fn mechanical(self: &Device) -> String;
-        // fn memory(self: &Device) -> String;
-        // fn platform(self: &Device) -> String;
-        // fn pcie_info(self: &Device) -> String;
-        // fn host(self: &Device) -> String;
-        // fn dynamic_regions(self: &Device) -> String;
+        fn electrical(self: &Device) -> Result<String>;
+        fn thermal(self: &Device) -> Result<String>;
+        fn mechanical(self: &Device) -> Result<String>;
+        fn memory(self: &Device) -> Result<String>;
+        fn platform(self: &Device) -> Result<String>;
+        fn pcie_info(self: &Device) -> Result<String>;
+        fn host(self: &Device) -> Result<String>;
+        fn dynamic_regions(self: &Device) -> Result<String>;
     }
 
     // xrt_xclbin.h
@@ -39,11 +39,19 @@ mod ffi {
         include!("src/ffi.h");
 
         type XclbinArg;
-        fn name(self: &XclbinArg) -> &str;
+        fn name(self: &XclbinArg) -> String;
         fn mems(self: &XclbinArg) -> UniquePtr<CxxVector<XclbinMem>>;
-        
+        #[rust_name = "index"]
+        fn get_index(self: &XclbinArg) -> usize;
+        #[rust_name = "offset"]
+        fn get_offset(self: &XclbinArg) -> u64;
+        #[rust_name = "size"]
+        fn get_size(self: &XclbinArg) -> u64;
+        fn host_type(self: &XclbinArg) -> String;
+        fn port(self: &XclbinArg) -> String;
+
         type XclbinIp;
-        fn name(self: &XclbinIp) -> &str;
+        fn name(self: &XclbinIp) -> String;
         #[rust_name = "num_args"]
         fn get_num_args(self: &XclbinIp) -> usize;
         fn args(self: &XclbinIp) -> UniquePtr<CxxVector<XclbinArg>>;
@@ -52,18 +60,30 @@ mod ffi {
         fn get_base_address(self: &XclbinIp) -> u64;
 
         type XclbinKernel;
-        fn name(self: &XclbinKernel) -> &str;
-        
+        fn name(self: &XclbinKernel) -> String;
+        fn args(self: &XclbinKernel) -> UniquePtr<CxxVector<XclbinArg>>;
+        fn cus(self: &XclbinKernel) -> UniquePtr<CxxVector<XclbinIp>>;
+
         type XclbinMem;
+        fn tag(self: &XclbinMem) -> String;
+        #[rust_name = "index"]
+        fn get_index(self: &XclbinMem) -> i32;
+        #[rust_name = "base_address"]
+        fn get_base_address(self: &XclbinMem) -> u64;
+        #[rust_name = "size_kb"]
+        fn get_size_kb(self: &XclbinMem) -> u64;
+        #[rust_name = "used"]
+        fn get_used(self: &XclbinMem) -> bool;
 
         type Xclbin;
-        fn new_xclbin(bytes: &[i8]) -> Result<UniquePtr<Xclbin>>;
+        fn new_xclbin(bytes: &[u8]) -> Result<UniquePtr<Xclbin>>;
         fn kernels(self: &Xclbin) -> UniquePtr<CxxVector<XclbinKernel>>;
         fn kernel(self: &Xclbin, name: &str) -> UniquePtr<XclbinKernel>;
         fn ips(self: &Xclbin) -> UniquePtr<CxxVector<XclbinIp>>;
         fn ip(self: &Xclbin, name: &str) -> UniquePtr<XclbinIp>;
-        fn xsa_name(self: &Xclbin) -> &str;
+        fn xsa_name(self: &Xclbin) -> String;
         fn uuid(self: &Xclbin) -> [u8; 16];
+        fn interface_uuid(self: &Xclbin) -> [u8; 16];
     }
 
     #[namespace = "xrt"]
@@ -87,12 +107,72 @@ mod ffi {
             name: &str,
             cu_access_mode: kernel_cu_access_mode,
         ) -> Result<UniquePtr<kernel>>;
-        fn group_id(self: &kernel, argno: i32) -> i32;
+        fn group_id(self: &kernel, argno: i32) -> Result<i32>;
         fn offset(self: &kernel, argno: i32) -> u32;
-        fn read_register(self: &kernel, offset: u32) -> u32;
+        fn read_register(self: &kernel, offset: u32) -> Result<u32>;
         fn write_register(self: Pin<&mut kernel>, offset: u32, data: u32) -> Result<()>;
     }
 
+    #[namespace = "xrt"]
+    #[repr(u32)]
+    enum bo_flags {
+        normal = 0,
+        cacheable = 0x0100_0000,
+        svm = 0x0800_0000,
+        device_only = 0x1000_0000,
+        host_only = 0x2000_0000,
+        p2p = 0x4000_0000,
+    }
+
+    // xrt_bo.h
+    #[namespace = "xrt"]
+    unsafe extern "C++" {
+        include!("src/ffi.h");
+
+        type bo_flags;
+
+        type Bo;
+        fn new_bo(
+            device: &Device,
+            size: usize,
+            flags: bo_flags,
+            group: u32,
+        ) -> Result<UniquePtr<Bo>>;
+        fn new_bo_import(device: &Device, handle: i32) -> Result<UniquePtr<Bo>>;
+        fn new_sub_bo(parent: &Bo, size: usize, offset: usize) -> Result<UniquePtr<Bo>>;
+        fn size(self: &Bo) -> usize;
+        fn address(self: &Bo) -> u64;
+
+        // Custom members
+        fn map(self: Pin<&mut Bo>) -> Result<&mut [u8]>;
+        fn sync_to_device(self: Pin<&mut Bo>, size: usize, offset: usize) -> Result<()>;
+        fn sync_from_device(self: Pin<&mut Bo>, size: usize, offset: usize) -> Result<()>;
+        fn write_bytes(self: Pin<&mut Bo>, data: &[u8], offset: usize) -> Result<()>;
+        fn read_bytes(self: Pin<&mut Bo>, data: &mut [u8], offset: usize) -> Result<()>;
+        fn export_handle(self: Pin<&mut Bo>) -> Result<i32>;
+    }
+
+    // xrt_kernel.h
+    #[namespace = "xrt"]
+    unsafe extern "C++" {
+        include!("src/ffi.h");
+
+        type Run;
+        fn new_run(kernel: &kernel) -> Result<UniquePtr<Run>>;
+        fn start(self: Pin<&mut Run>) -> Result<()>;
+
+        // Custom members
+        fn set_arg_scalar(self: Pin<&mut Run>, index: i32, bytes: &[u8]) -> Result<()>;
+        fn set_arg_bo(self: Pin<&mut Run>, index: i32, bo: &Bo) -> Result<()>;
+        fn wait_for(self: &Run, timeout_ms: u32) -> Result<u32>;
+        fn cmd_state(self: &Run) -> u32;
+        fn on_complete(
+            self: Pin<&mut Run>,
+            callback: fn(data: usize, state: u32),
+            data: usize,
+        ) -> Result<()>;
+    }
+
     // xrt_ip.h
     #[namespace = "xrt"]
     unsafe extern "C++" {
@@ -104,7 +184,7 @@ mod ffi {
             xclbin_id: [u8; 16],
             name: &str
         ) -> Result<UniquePtr<ip>>;
-        fn read_register(self: &ip, offset: u32) -> u32;
+        fn read_register(self: &ip, offset: u32) -> Result<u32>;
         fn write_register(self: Pin<&mut ip>, offset: u32, data: u32) -> Result<()>;
     }
 
@@ -115,6 +195,14 @@ mod ffi {
 
         fn set_ini(key: &str, value: &str) -> Result<()>;
     }
+
+    // Errors
+    #[namespace = "xrt"]
+    unsafe extern "C++" {
+        include!("src/ffi.h");
+
+        fn last_error_code() -> i32;
+    }
 }
 
 pub use ffi::*;
@@ -1,8 +1,50 @@
 use crate::Xrt;
 use serde::Deserialize;
-use std::str::FromStr;
+use std::{fs, path::Path, str::FromStr};
 use uuid::Uuid;
 
+/// A report of XRT, that can be parsed from the JSON returned by `get_info`
+/// or saved by `xbutil examine --format JSON`.
+pub trait Report: Sized {
+    /// Parses a report from `json`.
+    fn from_json(json: &str) -> crate::Result<Self>;
+
+    /// Parses a report from the JSON file at `path`.
+    fn from_file<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+macro_rules! impl_report {
+    ($($ty:ty),*) => {
+        $(
+            impl Report for $ty {
+                fn from_json(json: &str) -> crate::Result<Self> {
+                    Ok(serde_json::from_str(json)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_report!(
+    Electrical,
+    Thermals,
+    Mechanical,
+    Memory,
+    PCIeInfo,
+    Host,
+    DynamicRegions,
+    ExamineReport
+);
+
+impl Report for Platform {
+    fn from_json(json: &str) -> crate::Result<Self> {
+        let wrapper: Wrapper<Platform> = serde_json::from_str(json)?;
+        Ok(wrapper.inner)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Electrical {
     #[serde(deserialize_with = "parse")]
@@ -24,6 +66,35 @@ where
     })
 }
 
+/// Parses a string with a hexadecimal number prefixed with `0x`, or a decimal
+/// number.
+fn parse_int<'de, D: serde::de::Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(serde::de::Error::custom)
+}
+
+/// Parses a MAC address of six `:` separated hexadecimal bytes.
+fn parse_mac<'de, D: serde::de::Deserializer<'de>>(deserializer: D) -> Result<[u8; 6], D::Error> {
+    let s = String::deserialize(deserializer)?;
+    let invalid = || serde::de::Error::custom(format!("invalid MAC address: {}", s));
+    let mut address = [0; 6];
+    let mut bytes = s.split(':');
+    for byte in address.iter_mut() {
+        *byte = bytes
+            .next()
+            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+            .ok_or_else(invalid)?;
+    }
+    match bytes.next() {
+        Some(_) => Err(invalid()),
+        None => Ok(address),
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Power {
     pub id: String,
@@ -105,8 +176,10 @@ pub struct DirectMemoryAccesses {
 pub struct Metric {
     #[serde(deserialize_with = "parse")]
     pub channel_id: u8,
-    pub card_to_host_bytes: String, // todo
-    pub host_to_card_bytes: String, // todo
+    #[serde(deserialize_with = "parse_int")]
+    pub card_to_host_bytes: u64,
+    #[serde(deserialize_with = "parse_int")]
+    pub host_to_card_bytes: u64,
 }
 #[derive(Debug, Deserialize)]
 pub struct InnerMemory {
@@ -115,12 +188,14 @@ pub struct InnerMemory {
 }
 #[derive(Debug, Deserialize)]
 pub struct Mem {
-    pub base_address: String, // todo
+    #[serde(deserialize_with = "parse_int")]
+    pub base_address: u64,
     #[serde(deserialize_with = "parse")]
     pub enabled: bool,
     pub error_msg: Option<String>,
     pub extended_info: ExtendedInfo,
-    pub range_bytes: String, // todo
+    #[serde(deserialize_with = "parse_int")]
+    pub range_bytes: u64,
     pub tag: String,
     #[serde(rename(deserialize = "type"))]
     pub ty: String,
@@ -176,7 +251,8 @@ pub struct SatelliteController {
 
 #[derive(Debug, Deserialize)]
 pub struct Mac {
-    pub address: String, // todo [u8; 6]
+    #[serde(deserialize_with = "parse_mac")]
+    pub address: [u8; 6],
 }
 
 #[derive(Debug, Deserialize)]
@@ -232,8 +308,31 @@ pub struct DynamicRegions {
     pub xclbin_uuid: Uuid,
 }
 
+/// A report saved by `xbutil examine --format JSON`.
+#[derive(Debug, Deserialize)]
+pub struct ExamineReport {
+    #[serde(default)]
+    pub devices: Vec<DeviceReport>,
+}
+
+/// The report of a device in an [ExamineReport]. Only the sections that were
+/// requested with `--report` are present.
+#[derive(Debug, Deserialize)]
+pub struct DeviceReport {
+    /// The PCIe address of the device.
+    pub device_id: String,
+    pub electrical: Option<Electrical>,
+    pub thermals: Option<Vec<Thermal>>,
+    pub mechanical: Option<Mechanical>,
+    pub memory: Option<Memory>,
+    pub platform: Option<Platform>,
+    pub pcie_info: Option<PCIeInfo>,
+    pub host: Option<Host>,
+    pub dynamic_regions: Option<DynamicRegions>,
+}
+
 impl Xrt {
-    pub fn bdf(&self) -> &str {
+    pub fn bdf(&self) -> String {
         self.device.bdf()
     }
     pub fn interface_uuid(&self) -> Uuid {
@@ -248,7 +347,7 @@ impl Xrt {
     pub fn m2m(&self) -> bool {
         self.device.m2m()
     }
-    pub fn name(&self) -> &str {
+    pub fn name(&self) -> String {
         self.device.name()
     }
     pub fn nodma(&self) -> bool {
@@ -257,40 +356,208 @@ impl Xrt {
     pub fn offline(&self) -> bool {
         self.device.offline()
     }
-    // pub fn electrical(&self) -> Electrical {
-    //     let mut electrical: Electrical = serde_json::from_str(&self.device.electrical()).unwrap();
-    //     electrical.power_rails = electrical
-    //         .power_rails
-    //         .into_iter()
-    //         .filter(|power| power.current.is_present || power.voltage.is_present)
-    //         .collect();
-    //     electrical
-    // }
-    // pub fn thermal(&self) -> Vec<Thermal> {
-    //     let thermals: Thermals = serde_json::from_str(&self.device.thermal()).unwrap();
-    //     thermals
-    //         .thermals
-    //         .into_iter()
-    //         .filter(|thermal| thermal.is_present)
-    //         .collect()
-    // }
-    // pub fn mechanical(&self) -> Mechanical {
-    //     serde_json::from_str(&self.device.mechanical()).unwrap()
-    // }
-    // pub fn memory(&self) -> Memory {
-    //     serde_json::from_str(&self.device.memory()).unwrap()
-    // }
-    // pub fn platform(&self) -> Platform {
-    //     let wrapper: Wrapper<Platform> = serde_json::from_str(&self.device.platform()).unwrap();
-    //     wrapper.inner
-    // }
-    // pub fn pcie_info(&self) -> PCIeInfo {
-    //     serde_json::from_str(&self.device.pcie_info()).unwrap()
-    // }
-    // pub fn host(&self) -> Host {
-    //     serde_json::from_str(&self.device.host()).unwrap()
-    // }
-    // pub fn dynamic_regions(&self) -> DynamicRegions {
-    //     serde_json::from_str(&self.device.dynamic_regions()).unwrap()
-    // }
+    pub fn electrical(&self) -> crate::Result<Electrical> {
+        let mut electrical = Electrical::from_json(&self.device.electrical()?)?;
+        electrical.power_rails = electrical
+            .power_rails
+            .into_iter()
+            .filter(|power| power.current.is_present || power.voltage.is_present)
+            .collect();
+        Ok(electrical)
+    }
+    pub fn thermal(&self) -> crate::Result<Vec<Thermal>> {
+        let thermals = Thermals::from_json(&self.device.thermal()?)?;
+        Ok(thermals
+            .thermals
+            .into_iter()
+            .filter(|thermal| thermal.is_present)
+            .collect())
+    }
+    pub fn mechanical(&self) -> crate::Result<Mechanical> {
+        Mechanical::from_json(&self.device.mechanical()?)
+    }
+    pub fn memory(&self) -> crate::Result<Memory> {
+        Memory::from_json(&self.device.memory()?)
+    }
+    /// Returns the platform report of the device. Not to be confused with
+    /// [fpga_core::Platform::platform], which returns the platform type.
+    pub fn platform_report(&self) -> crate::Result<Platform> {
+        Platform::from_json(&self.device.platform()?)
+    }
+    pub fn pcie_info(&self) -> crate::Result<PCIeInfo> {
+        PCIeInfo::from_json(&self.device.pcie_info()?)
+    }
+    pub fn host(&self) -> crate::Result<Host> {
+        Host::from_json(&self.device.host()?)
+    }
+    pub fn dynamic_regions(&self) -> crate::Result<DynamicRegions> {
+        DynamicRegions::from_json(&self.device.dynamic_regions()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn electrical() {
+        let electrical = Electrical::from_json(
+            r#"{
+                "power_consumption_max_watts": "225",
+                "power_consumption_warning": "false",
+                "power_consumption_watts": "24.5",
+                "power_rails": [{
+                    "id": "12v_pex",
+                    "description": "12 Volts PCI Express",
+                    "voltage": { "volts": "12.1", "is_present": "true" },
+                    "current": { "amps": "1.5", "is_present": "true" }
+                }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(electrical.power_consumption_watts, 24.5);
+        assert!(!electrical.power_consumption_warning);
+        assert_eq!(electrical.power_rails[0].voltage.volts, 12.1);
+        assert!(Electrical::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn examine_report() {
+        let report = ExamineReport::from_json(
+            r#"{
+                "schema_version": { "schema": "JSON", "creation_date": "" },
+                "devices": [{
+                    "interface_type": "pcie",
+                    "device_id": "0000:3b:00.1",
+                    "thermals": [{
+                        "location_id": "fpga0",
+                        "description": "FPGA",
+                        "temp_C": "41",
+                        "is_present": "true"
+                    }],
+                    "memory": {
+                        "board": {
+                            "direct_memory_accesses": {
+                                "type": "pcie xdma",
+                                "metrics": [{
+                                    "channel_id": "0",
+                                    "card_to_host_bytes": "0x1000",
+                                    "host_to_card_bytes": "0x0"
+                                }]
+                            },
+                            "memory": {
+                                "data_streams": [],
+                                "memories": [{
+                                    "type": "MEM_DDR4",
+                                    "tag": "bank0",
+                                    "enabled": "true",
+                                    "base_address": "0x4000000000",
+                                    "range_bytes": "0x400000000",
+                                    "extended_info": {
+                                        "usage": {
+                                            "allocated_bytes": "0",
+                                            "buffer_objects_count": "0"
+                                        }
+                                    }
+                                }]
+                            }
+                        }
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+        let device = &report.devices[0];
+        assert_eq!(device.device_id, "0000:3b:00.1");
+        assert_eq!(device.thermals.as_ref().unwrap()[0].temp_c, 41);
+        assert!(device.electrical.is_none());
+
+        let board = device.memory.as_ref().unwrap().board.as_ref().unwrap();
+        assert_eq!(
+            board.direct_memory_accesses.metrics[0].card_to_host_bytes,
+            0x1000
+        );
+        let memory = &board.memory.memories[0];
+        assert_eq!(memory.base_address, 0x40_0000_0000);
+        assert_eq!(memory.range_bytes, 16 << 30);
+    }
+
+    /// Returns the path of the report fixture `name`.
+    fn fixture(name: &str) -> std::path::PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    #[test]
+    fn examine_report_from_file() {
+        let report = ExamineReport::from_file(fixture("examine-u250.json")).unwrap();
+        assert_eq!(report.devices.len(), 1);
+        let device = &report.devices[0];
+        assert_eq!(device.device_id, "0000:3b:00.1");
+
+        let electrical = device.electrical.as_ref().unwrap();
+        assert_eq!(electrical.power_consumption_max_watts, 225.);
+        assert_eq!(electrical.power_consumption_watts, 25.904);
+        assert_eq!(electrical.power_rails.len(), 5);
+        assert!(!electrical.power_rails[1].current.is_present);
+
+        let thermals = device.thermals.as_ref().unwrap();
+        assert_eq!(thermals[2].location_id, "fpga0");
+        assert_eq!(thermals[2].temp_c, 43);
+        assert!(!thermals[4].is_present);
+
+        let fan = &device.mechanical.as_ref().unwrap().fans[0];
+        assert_eq!(fan.critical_trigger_temp_c, 65);
+        assert!(!fan.is_present);
+
+        let board = device.memory.as_ref().unwrap().board.as_ref().unwrap();
+        assert_eq!(board.direct_memory_accesses.ty, "pcie xdma");
+        assert_eq!(
+            board.direct_memory_accesses.metrics[0].host_to_card_bytes,
+            0x40000
+        );
+        let memories = &board.memory.memories;
+        assert_eq!(memories.len(), 3);
+        assert_eq!(memories[1].tag, "bank1");
+        assert_eq!(memories[1].base_address, 0x50_0000_0000);
+        assert_eq!(memories[1].extended_info.usage.buffer_objects_count, 2);
+        assert!(!memories[2].enabled);
+
+        let platform = device.platform.as_ref().unwrap();
+        assert_eq!(
+            platform.static_region.vbnv,
+            "xilinx_u250_gen3x16_xdma_shell_3_1"
+        );
+        assert_eq!(platform.off_chip_board_info.ddr_count, 4);
+        assert_eq!(platform.off_chip_board_info.ddr_size_bytes, 64 << 30);
+        assert!(platform.status.mig_calibrated);
+        assert_eq!(platform.macs[1].address, [0x00, 0x0a, 0x35, 0, 0, 0x11]);
+
+        let pcie_info = device.pcie_info.as_ref().unwrap();
+        assert_eq!(pcie_info.vendor, "0x10ee");
+        assert_eq!(pcie_info.link_speed_gbit_sec, 8);
+        assert_eq!(pcie_info.express_lane_width_count, 16);
+
+        assert!(device.host.is_none());
+        assert_eq!(
+            device.dynamic_regions.as_ref().unwrap().xclbin_uuid,
+            Uuid::parse_str("a9a1f0c6-2a8b-4c1e-bf4d-7e6c3f0d1b22").unwrap()
+        );
+    }
+
+    #[test]
+    fn examine_report_from_file_without_devices() {
+        let report = ExamineReport::from_file(fixture("examine-empty.json")).unwrap();
+        assert!(report.devices.is_empty());
+        assert!(ExamineReport::from_file(fixture("missing.json")).is_err());
+    }
+
+    #[test]
+    fn mac() {
+        let mac: Mac = serde_json::from_str(r#"{ "address": "00:0A:35:06:1F:40" }"#).unwrap();
+        assert_eq!(mac.address, [0x00, 0x0a, 0x35, 0x06, 0x1f, 0x40]);
+        assert!(serde_json::from_str::<Mac>(r#"{ "address": "00:0A:35:06:1F" }"#).is_err());
+        assert!(serde_json::from_str::<Mac>(r#"{ "address": "00:0A:35:06:1F:40:00" }"#).is_err());
+    }
 }
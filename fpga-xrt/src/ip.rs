@@ -1,6 +1,42 @@
-use crate::ffi;
+use crate::{ffi, Error, IpConfiguration, Result, Xrt};
 use cxx::UniquePtr;
+use fpga_core::{DynMMIO, Platform, PlatformType};
 
+/// An IP of an xclbin, returned by [Xrt::ip].
 pub struct Ip {
     pub(crate) ip: UniquePtr<ffi::ip>,
 }
+
+impl Ip {
+    /// Reads the 32-bit register at `offset` of this IP.
+    pub fn read_register(&self, offset: u32) -> Result<u32> {
+        Ok(self.ip.read_register(offset)?)
+    }
+
+    /// Writes `value` to the 32-bit register at `offset` of this IP.
+    pub fn write_register(&mut self, offset: u32, value: u32) -> Result<()> {
+        Ok(self.ip.pin_mut().write_register(offset, value)?)
+    }
+}
+
+impl Platform for Ip {
+    type Configuration = IpConfiguration;
+    type Error = Error;
+
+    fn from_configuration(configuration: Self::Configuration) -> Result<Self> {
+        Xrt::from_configuration(configuration.device)?
+            .ip(configuration.xclbin_uuid, &configuration.name)
+    }
+
+    fn platform(&self) -> PlatformType {
+        PlatformType::XRT
+    }
+
+    fn dyn_mmio(&self) -> Option<&dyn DynMMIO> {
+        Some(self)
+    }
+
+    fn dyn_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        Some(self)
+    }
+}
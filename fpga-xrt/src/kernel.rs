@@ -1,6 +1,276 @@
-use crate::ffi;
+use crate::{ffi, Error, IpConfiguration, IpMetadata, KernelMetadata, Result, Run, Xclbin, Xrt};
 use cxx::UniquePtr;
+use fpga_core::{DynMMIO, Platform, PlatformType};
+use uuid::Uuid;
+
+/// Access mode of the compute units of a kernel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessMode {
+    /// The compute units can not be opened by other processes.
+    Exclusive,
+    /// The compute units can be opened by other processes with shared access.
+    Shared,
+    /// The compute units are not acquired, e.g. to only access registers.
+    None,
+}
+
+impl From<AccessMode> for ffi::kernel_cu_access_mode {
+    fn from(mode: AccessMode) -> Self {
+        match mode {
+            AccessMode::Exclusive => Self::exclusive,
+            AccessMode::Shared => Self::shared,
+            AccessMode::None => Self::none,
+        }
+    }
+}
+
+/// Builder to open a kernel, returned by [Xrt::kernel_builder].
+#[derive(Debug)]
+pub struct KernelBuilder<'xrt> {
+    xrt: &'xrt Xrt,
+    uuid: Uuid,
+    name: String,
+    access_mode: AccessMode,
+    compute_units: Vec<String>,
+    xclbin: Option<&'xrt Xclbin>,
+}
+
+impl<'xrt> KernelBuilder<'xrt> {
+    pub(crate) fn new(xrt: &'xrt Xrt, uuid: Uuid, name: &str) -> Self {
+        Self {
+            xrt,
+            uuid,
+            name: name.to_string(),
+            access_mode: AccessMode::Exclusive,
+            compute_units: Vec::new(),
+            xclbin: None,
+        }
+    }
+
+    /// Sets the access mode of the compute units. Defaults to
+    /// [AccessMode::Exclusive].
+    pub fn with_access_mode(mut self, access_mode: AccessMode) -> Self {
+        self.access_mode = access_mode;
+        self
+    }
+
+    /// Selects the compute units with `names`, e.g. `vadd_1`, instead of all
+    /// compute units of the kernel.
+    pub fn with_compute_units<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.compute_units.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the xclbin to get the metadata of the kernel from, e.g. the names
+    /// of its arguments. Defaults to the xclbin loaded with
+    /// [Program::program], which is unknown if the xclbin was loaded by
+    /// another process or [Xrt] instance.
+    ///
+    /// [Program::program]: fpga_core::Program::program
+    pub fn with_xclbin(mut self, xclbin: &'xrt Xclbin) -> Self {
+        self.xclbin.replace(xclbin);
+        self
+    }
+
+    /// Returns the name used by XRT to select the compute units, e.g.
+    /// `vadd:{vadd_1,vadd_2}`.
+    fn pattern(&self) -> String {
+        if self.compute_units.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{}:{{{}}}", self.name, self.compute_units.join(","))
+        }
+    }
+
+    /// Returns true if compute unit `cu`, e.g. `vadd:vadd_1`, is selected.
+    fn selects(&self, cu: &str) -> bool {
+        let name = cu.rsplit(':').next().unwrap_or(cu);
+        self.compute_units.is_empty() || self.compute_units.iter().any(|selected| selected == name)
+    }
+
+    /// Opens the kernel, acquiring the selected compute units.
+    pub fn build(self) -> Result<Kernel> {
+        let kernel = ffi::new_kernel(
+            &self.xrt.device,
+            *self.uuid.as_bytes(),
+            &self.pattern(),
+            self.access_mode.into(),
+        )
+        .map_err(|exception| Error::kernel(&self.name, exception))?;
+
+        let metadata = self
+            .xclbin
+            .or_else(|| self.xrt.xclbin.as_ref())
+            .filter(|xclbin| xclbin.uuid() == self.uuid)
+            .and_then(|xclbin| xclbin.kernel(&self.name))
+            .map(|mut metadata| {
+                metadata.args.sort_by_key(|arg| arg.index);
+                metadata.compute_units.retain(|cu| self.selects(&cu.name));
+                metadata
+            });
+
+        Ok(Kernel {
+            kernel,
+            name: self.name,
+            access_mode: self.access_mode,
+            metadata,
+        })
+    }
+}
 
 pub struct Kernel {
     pub(crate) kernel: UniquePtr<ffi::kernel>,
+    name: String,
+    access_mode: AccessMode,
+    /// Metadata with the arguments in order of index and only the selected
+    /// compute units, if known.
+    metadata: Option<KernelMetadata>,
+}
+
+impl Kernel {
+    /// Returns the memory bank connected to argument `argno`, which is used to
+    /// allocate buffers for that argument.
+    pub fn group_id(&self, argno: usize) -> Result<u32> {
+        Ok(self.kernel.group_id(argno as i32)? as u32)
+    }
+
+    /// Returns the access mode of the compute units of this kernel.
+    pub fn access_mode(&self) -> AccessMode {
+        self.access_mode
+    }
+
+    /// Returns the xclbin metadata of the compute units selected by the
+    /// builder of this kernel. This is not queried from XRT, so it does not
+    /// reflect whether XRT could acquire them. Returns an error if the
+    /// metadata is unknown, see [KernelBuilder::with_xclbin].
+    pub fn compute_unit_metadata(&self) -> Result<&[IpMetadata]> {
+        self.metadata()
+            .map(|metadata| metadata.compute_units.as_slice())
+    }
+
+    fn metadata(&self) -> Result<&KernelMetadata> {
+        self.metadata
+            .as_ref()
+            .ok_or_else(|| Error::MissingMetadata(self.name.clone()))
+    }
+
+    /// Reads the 32-bit register at `offset` of the compute units.
+    pub fn read_register(&self, offset: u32) -> Result<u32> {
+        Ok(self.kernel.read_register(offset)?)
+    }
+
+    /// Writes `value` to the 32-bit register at `offset` of the compute
+    /// units. This requires [AccessMode::Exclusive].
+    pub fn write_register(&mut self, offset: u32, value: u32) -> Result<()> {
+        Ok(self.kernel.pin_mut().write_register(offset, value)?)
+    }
+
+    /// Returns the index of argument `name`. Returns an error if the metadata
+    /// of this kernel is unknown, see [KernelBuilder::with_xclbin].
+    pub fn arg_index(&self, name: &str) -> Result<usize> {
+        self.metadata()?
+            .args
+            .iter()
+            .find(|arg| arg.name == name)
+            .map(|arg| arg.index)
+            .ok_or_else(|| Error::UnknownArgument(name.to_string()))
+    }
+
+    /// Returns a new run of this kernel. A run can be started repeatedly,
+    /// keeping the arguments of previous starts.
+    pub fn run(&self) -> Result<Run<'_>> {
+        Run::new(self)
+    }
+}
+
+impl Platform for Kernel {
+    type Configuration = IpConfiguration;
+    type Error = Error;
+
+    fn from_configuration(configuration: Self::Configuration) -> Result<Self> {
+        Xrt::from_configuration(configuration.device)?
+            .kernel(configuration.xclbin_uuid, &configuration.name)
+    }
+
+    fn platform(&self) -> PlatformType {
+        PlatformType::XRT
+    }
+
+    fn dyn_mmio(&self) -> Option<&dyn DynMMIO> {
+        Some(self)
+    }
+
+    fn dyn_mmio_mut(&mut self) -> Option<&mut dyn DynMMIO> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArgMetadata;
+
+    fn arg(index: usize, name: &str) -> ArgMetadata {
+        ArgMetadata {
+            index,
+            name: name.to_string(),
+            offset: 0x10 + 8 * index as u64,
+            size: 4,
+            host_type: "int".to_string(),
+            port: "s_axi_control".to_string(),
+            mems: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pattern() {
+        let xrt = Xrt {
+            device: UniquePtr::null(),
+            xclbin: None,
+        };
+        let builder = KernelBuilder::new(&xrt, Uuid::nil(), "vadd");
+        assert_eq!(builder.pattern(), "vadd");
+        assert!(builder.selects("vadd:vadd_1"));
+
+        let builder = builder.with_compute_units(vec!["vadd_1", "vadd_3"]);
+        assert_eq!(builder.pattern(), "vadd:{vadd_1,vadd_3}");
+        assert!(builder.selects("vadd:vadd_1"));
+        assert!(builder.selects("vadd_3"));
+        assert!(!builder.selects("vadd:vadd_2"));
+        assert!(!builder.selects("vadd:vadd_10"));
+    }
+
+    #[test]
+    fn arg_index() {
+        let kernel = Kernel {
+            kernel: UniquePtr::null(),
+            name: "vadd".to_string(),
+            access_mode: AccessMode::None,
+            metadata: Some(KernelMetadata {
+                name: "vadd".to_string(),
+                compute_units: Vec::new(),
+                // Argument 1 is not exposed to the host.
+                args: vec![arg(0, "a"), arg(2, "c")],
+            }),
+        };
+        assert_eq!(kernel.arg_index("a").unwrap(), 0);
+        assert_eq!(kernel.arg_index("c").unwrap(), 2);
+        assert!(matches!(
+            kernel.arg_index("b"),
+            Err(Error::UnknownArgument(_))
+        ));
+
+        let kernel = Kernel {
+            metadata: None,
+            ..kernel
+        };
+        assert!(matches!(
+            kernel.arg_index("a"),
+            Err(Error::MissingMetadata(_))
+        ));
+    }
 }
@@ -1,11 +1,14 @@
 use std::pin::Pin;
 
-use cxx::{Exception, UniquePtr};
-use fpga_core::{Platform, PlatformType, Power, Program, Thermal};
+use cxx::UniquePtr;
+use fpga_core::{DynPower, DynProgram, DynThermal, Platform, PlatformType, Program};
 use uuid::Uuid;
 
 pub(crate) mod ffi;
 
+mod buffer;
+pub use buffer::*;
+
 mod info;
 pub use info::*;
 
@@ -18,93 +21,138 @@ pub use kernel::*;
 mod ip;
 pub use ip::*;
 
-type Result<T> = std::result::Result<T, Exception>;
+mod mmio;
+
+mod run;
+pub use run::*;
+
+mod errors;
+pub use errors::*;
 
 pub struct Xrt {
     device: UniquePtr<ffi::Device>,
+    /// The xclbin loaded with [Program::program], if any.
+    xclbin: Option<Xclbin>,
 }
 
 impl Xrt {
-    pub fn kernel(&self, uuid: Uuid, name: &str) -> Result<Kernel> {
-        Ok(Kernel {
-            kernel: ffi::new_kernel(
-                &self.device,
-                *uuid.as_bytes(),
-                name,
-                ffi::kernel_cu_access_mode::exclusive,
-            )?,
+    /// Opens IP `name` of the xclbin with `uuid`, e.g. a compute unit
+    /// `vadd:vadd_1` or an RTL kernel without a control protocol known to XRT.
+    pub fn ip(&self, uuid: Uuid, name: &str) -> Result<Ip> {
+        Ok(Ip {
+            ip: ffi::new_ip(&self.device, *uuid.as_bytes(), name)
+                .map_err(|exception| Error::kernel(name, exception))?,
         })
     }
+
+    /// Opens all compute units of kernel `name` with exclusive access. Use
+    /// [Xrt::kernel_builder] to select the access mode and compute units.
+    pub fn kernel(&self, uuid: Uuid, name: &str) -> Result<Kernel> {
+        self.kernel_builder(uuid, name).build()
+    }
+
+    /// Returns a builder to open kernel `name` of the xclbin with `uuid`.
+    pub fn kernel_builder(&self, uuid: Uuid, name: &str) -> KernelBuilder<'_> {
+        KernelBuilder::new(self, uuid, name)
+    }
 }
 
 pub enum Configuration {
     DeviceIndex(usize),
+    /// The PCIe address of the device, e.g. `0000:3b:00.1`.
     Bdf(String),
+    /// The first device with this shell name. Names separated by `_` or `:`
+    /// match, e.g. `xilinx_u250_gen3x16_xdma_shell_3_1` and
+    /// `xilinx:u250:gen3x16:xdma:shell:3.1`.
+    Shell(String),
+    /// The first device with an xclbin with this UUID loaded.
+    XclbinUuid(Uuid),
+    /// The first device with a shell with this interface UUID.
+    InterfaceUuid(Uuid),
+}
+
+/// Configuration of a [Kernel] or [Ip] as [Platform], to access its registers
+/// with [MMIO](fpga_core::MMIO). The xclbin must be loaded on the device.
+pub struct IpConfiguration {
+    pub device: Configuration,
+    pub xclbin_uuid: Uuid,
+    pub name: String,
 }
 
 impl Platform for Xrt {
     type Configuration = Configuration;
-    type Error = Exception;
+    type Error = Error;
 
     fn from_configuration(configuration: Self::Configuration) -> Result<Self> {
         match configuration {
             Configuration::DeviceIndex(index) => Self::from_device_index(index),
-            Configuration::Bdf(_) => todo!(),
+            Configuration::Bdf(bdf) => Self::from_bdf(&bdf),
+            Configuration::Shell(name) => Self::find(|xrt| same_shell(&xrt.name(), &name)),
+            Configuration::XclbinUuid(uuid) => Self::find(|xrt| xrt.xclbin_uuid() == uuid),
+            Configuration::InterfaceUuid(uuid) => Self::find(|xrt| xrt.interface_uuid() == uuid),
         }
     }
 
     fn platform(&self) -> PlatformType {
         PlatformType::XRT
     }
+
+    fn dyn_power(&self) -> Option<&dyn DynPower> {
+        Some(self)
+    }
+
+    fn dyn_thermal(&self) -> Option<&dyn DynThermal> {
+        Some(self)
+    }
+
+    fn dyn_program(&mut self) -> Option<&mut dyn DynProgram> {
+        Some(self)
+    }
 }
 
 impl Program for Xrt {
     type Source = Xclbin;
     type Output = Uuid;
 
-    fn program(&mut self, source: &Self::Source) -> Result<Self::Output> {
-        // todo(mb): validate that the source targets this device
-        Ok(Uuid::from_bytes(
-            self.device.pin_mut().load(&source.xclbin),
-        ))
+    fn program(&mut self, source: Self::Source) -> Result<Self::Output> {
+        self.check_compatible(&source)?;
+        let uuid = source.uuid();
+        // Reloading the xclbin that is already loaded only resets the device.
+        if self.xclbin_uuid() != uuid {
+            self.device.pin_mut().load(&source.xclbin)?;
+        }
+        self.xclbin.replace(source);
+        Ok(uuid)
+    }
+}
+
+// Qualified to not shadow the Power and Thermal reports of the info module.
+impl fpga_core::Power for Xrt {
+    fn power(&self) -> Result<f32> {
+        self.electrical()
+            .map(|electrical| electrical.power_consumption_watts)
     }
 }
 
-// impl Power for Xrt {
-//     fn power(&self) -> f32 {
-//         self.electrical().power_consumption_watts
-//     }
-// }
-
-// impl Thermal for Xrt {
-//     fn temperature(&self) -> f32 {
-//         self.thermal()
-//             .into_iter()
-//             .find(|thermal| thermal.location_id == "fpga0")
-//             .map(|thermal| thermal.temp_c)
-//             .unwrap_or_default() as f32
-//     }
-// }
+impl fpga_core::Thermal for Xrt {
+    fn temperature(&self) -> Result<f32> {
+        self.thermal()?
+            .into_iter()
+            .find(|thermal| thermal.location_id == "fpga0")
+            .map(|thermal| f32::from(thermal.temp_c))
+            .ok_or_else(|| Error::SensorNotFound("fpga0".to_string()))
+    }
+}
 
+/// Formats the identity of the device only, as the other device info is
+/// queried from the driver and may fail.
 impl std::fmt::Debug for Xrt {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Xrt")
             .field("bdf", &self.bdf())
-            .field("interface_uuid", &self.interface_uuid())
-            .field("kdma", &self.kdma())
-            .field("max_clock_frequency_mhz", &self.max_clock_frequency_mhz())
-            .field("m2m", &self.m2m())
             .field("name", &self.name())
-            .field("nodma", &self.nodma())
-            .field("offline", &self.offline())
-            // .field("electrical", &self.electrical())
-            // .field("thermal", &self.thermal())
-            // .field("mechanical", &self.mechanical())
-            // .field("memory", &self.memory())
-            // .field("platform", &self.platform())
-            // .field("pcie_info", &self.pcie_info())
-            // .field("host", &self.host())
-            // .field("dynamic_regions", &self.dynamic_regions())
+            .field("interface_uuid", &self.interface_uuid())
+            .field("xclbin_uuid", &self.xclbin_uuid())
             .finish()
     }
 }
@@ -118,19 +166,91 @@ impl Xrt {
         ffi::set_ini("Runtime.runtime_log", "null")?;
 
         Ok(Self {
-            device: ffi::new_device(index as u32)?,
+            device: ffi::new_device(index as u32).map_err(Error::device_open)?,
+            xclbin: None,
         })
     }
 
+    /// Returns all devices on this system, in order of device index.
+    pub fn enumerate() -> Vec<Self> {
+        (0..)
+            .map(Self::from_device_index)
+            .take_while(Result::is_ok)
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    /// Returns the first device for which `predicate` returns true.
+    pub fn find<P: FnMut(&Self) -> bool>(mut predicate: P) -> Result<Self> {
+        let devices = Self::enumerate();
+        let available = devices
+            .iter()
+            .map(|xrt| format!("{} ({})", xrt.bdf(), xrt.name()))
+            .collect();
+        devices
+            .into_iter()
+            .find(|xrt| predicate(xrt))
+            .ok_or(Error::NoMatchingDevice { available })
+    }
+
     pub fn from_bdf(bdf: &str) -> Result<Self> {
         ffi::set_ini("Runtime.runtime_log", "null")?;
 
         Ok(Self {
-            device: ffi::new_device_bdf(bdf)?,
+            device: ffi::new_device_bdf(bdf).map_err(Error::device_open)?,
+            xclbin: None,
         })
     }
 
     pub fn xclbin_uuid(&self) -> Uuid {
         Uuid::from_bytes(self.device.xclbin_uuid())
     }
+
+    /// Returns an error if `xclbin` does not target the shell of this device.
+    ///
+    /// The interface UUIDs are compared when the xclbin has one, otherwise
+    /// the shell names are compared.
+    pub fn check_compatible(&self, xclbin: &Xclbin) -> Result<()> {
+        let interface_uuid = xclbin.interface_uuid();
+        if !interface_uuid.is_nil() {
+            let device = self.interface_uuid();
+            if device != interface_uuid {
+                return Err(Error::IncompatibleXclbin {
+                    device: device.to_string(),
+                    xclbin: interface_uuid.to_string(),
+                });
+            }
+        } else {
+            let (device, xsa_name) = (self.name(), xclbin.xsa_name());
+            if !xsa_name.is_empty() && !same_shell(&device, &xsa_name) {
+                return Err(Error::IncompatibleXclbin {
+                    device,
+                    xclbin: xsa_name,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if `a` and `b` name the same shell. Shell names are either
+/// separated by `_`, or by `:` with a `.` in the version.
+fn same_shell(a: &str, b: &str) -> bool {
+    let normalize = |name: &str| name.replace(&[':', '.'][..], "_").to_lowercase();
+    normalize(a) == normalize(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shell_names() {
+        let name = "xilinx_u250_gen3x16_xdma_shell_3_1";
+        assert!(same_shell(name, name));
+        assert!(same_shell(name, "xilinx:u250:gen3x16:xdma:shell:3.1"));
+        assert!(same_shell(name, "Xilinx:U250:gen3x16_xdma_shell:3.1"));
+        assert!(!same_shell(name, "xilinx_u250_gen3x16_xdma_shell_3_0"));
+        assert!(!same_shell(name, "xilinx_u250_xdma_201830_2"));
+    }
 }
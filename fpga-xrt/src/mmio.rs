@@ -0,0 +1,68 @@
+use crate::{Error, Ip, Kernel, Result};
+use fpga_core::{MmioError, MMIO};
+use std::convert::TryFrom;
+
+/// Returns the size of the register space of a kernel or IP. XRT addresses
+/// registers with 32-bit offsets.
+fn space_size() -> Option<usize> {
+    usize::try_from(1u64 << 32).ok()
+}
+
+/// Returns the 32-bit offset of the register `index` words after `offset`.
+fn register(offset: usize, index: u32) -> Result<u32> {
+    u32::try_from(offset)
+        .ok()
+        .and_then(|offset| offset.checked_add(index * 4))
+        .ok_or_else(|| {
+            Error::Mmio(MmioError::OutOfBounds {
+                offset,
+                len: (index as usize + 1) * 4,
+                size: space_size().unwrap_or(usize::MAX),
+            })
+        })
+}
+
+/// Implements [MMIO] for a type with `read_register` and `write_register`
+/// methods, exposing its registers as a single address space. 64-bit
+/// accesses are split in two 32-bit accesses, low word first.
+macro_rules! impl_mmio {
+    ($($ty:ty),*) => {
+        $(
+            impl MMIO for $ty {
+                fn num_spaces(&self) -> u32 {
+                    1
+                }
+
+                fn space_size(&self, _space: u32) -> Option<usize> {
+                    space_size()
+                }
+
+                fn read_u32(&self, space: u32, offset: usize) -> Result<u32> {
+                    self.check_access(space, offset, 4, 4)?;
+                    self.read_register(register(offset, 0)?)
+                }
+
+                fn read_u64(&self, space: u32, offset: usize) -> Result<u64> {
+                    self.check_access(space, offset, 8, 8)?;
+                    let low = self.read_register(register(offset, 0)?)?;
+                    let high = self.read_register(register(offset, 1)?)?;
+                    Ok(u64::from(high) << 32 | u64::from(low))
+                }
+
+                fn write_u32(&mut self, space: u32, offset: usize, value: u32) -> Result<()> {
+                    self.check_access(space, offset, 4, 4)?;
+                    self.write_register(register(offset, 0)?, value)
+                }
+
+                fn write_u64(&mut self, space: u32, offset: usize, value: u64) -> Result<()> {
+                    self.check_access(space, offset, 8, 8)?;
+                    self.write_register(register(offset, 0)?, value as u32)?;
+                    self.write_register(register(offset, 1)?, (value >> 32) as u32)
+                }
+            }
+        )*
+    };
+}
+
+// The registers of the compute units of a kernel, or of an IP.
+impl_mmio!(Kernel, Ip);
@@ -0,0 +1,308 @@
+use crate::{ffi, Buffer, Error, Kernel, Result};
+use cxx::UniquePtr;
+use std::{
+    convert::TryFrom,
+    fmt::Debug,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+/// State of a kernel run, as reported by the embedded runtime (`ert_cmd_state`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunState {
+    New,
+    Queued,
+    Running,
+    Completed,
+    Error,
+    Abort,
+    Submitted,
+    Timeout,
+    NoResponse,
+    /// A state not known to this crate.
+    Other(u32),
+}
+
+impl RunState {
+    /// Returns true if the run is no longer queued or running.
+    pub fn is_finished(self) -> bool {
+        !matches!(
+            self,
+            Self::New | Self::Queued | Self::Running | Self::Submitted
+        )
+    }
+}
+
+impl From<u32> for RunState {
+    fn from(state: u32) -> Self {
+        match state {
+            1 => Self::New,
+            2 => Self::Queued,
+            3 => Self::Running,
+            4 => Self::Completed,
+            5 => Self::Error,
+            6 => Self::Abort,
+            7 => Self::Submitted,
+            8 => Self::Timeout,
+            9 => Self::NoResponse,
+            other => Self::Other(other),
+        }
+    }
+}
+
+/// A scalar kernel argument.
+pub trait Scalar: Copy {
+    /// Returns the bytes of this value, as passed to the kernel.
+    fn to_bytes(self) -> Vec<u8>;
+}
+
+macro_rules! impl_scalar {
+    ($($ty:ty),*) => {
+        $(
+            impl Scalar for $ty {
+                fn to_bytes(self) -> Vec<u8> {
+                    self.to_ne_bytes().to_vec()
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+
+/// Completion state of a run, shared with the XRT completion callback.
+#[derive(Debug, Default)]
+struct Notify {
+    state: Option<RunState>,
+    waker: Option<Waker>,
+}
+
+/// Completion callback of a run. `data` points to the `Mutex<Notify>` of the
+/// run, which is kept alive until the run is no longer in flight, see the
+/// [Drop] implementation of [Run].
+fn complete(data: usize, state: u32) {
+    let notify = unsafe { &*(data as *const Mutex<Notify>) };
+    if let Ok(mut notify) = notify.lock() {
+        notify.state.replace(RunState::from(state));
+        if let Some(waker) = notify.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [Run::start_async] that resolves to the final state of
+/// the run.
+///
+/// The future is woken by XRT when the run completes, so it works with any
+/// executor and does not block a thread.
+#[derive(Debug)]
+pub struct Completion<'run> {
+    notify: Arc<Mutex<Notify>>,
+    _run: PhantomData<&'run mut ()>,
+}
+
+impl Future for Completion<'_> {
+    type Output = RunState;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut notify = self.notify.lock().unwrap();
+        match notify.state.take() {
+            Some(state) => Poll::Ready(state),
+            None => {
+                notify.waker.replace(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Returns `timeout` in milliseconds as passed to XRT, where zero waits
+/// indefinitely. Timeouts are rounded down, but at least 1 ms, and saturate at
+/// `u32::MAX` ms.
+fn timeout_ms(timeout: Option<Duration>) -> u32 {
+    timeout
+        .map(|timeout| {
+            u32::try_from(timeout.as_millis())
+                .unwrap_or(u32::MAX)
+                .max(1)
+        })
+        .unwrap_or_default()
+}
+
+/// A run of a [Kernel], which holds the arguments of the kernel and can be
+/// started repeatedly.
+pub struct Run<'kernel> {
+    run: UniquePtr<ffi::Run>,
+    kernel: &'kernel Kernel,
+    notify: Option<Arc<Mutex<Notify>>>,
+}
+
+impl<'kernel> Run<'kernel> {
+    pub(crate) fn new(kernel: &'kernel Kernel) -> Result<Self> {
+        Ok(Self {
+            run: ffi::new_run(&kernel.kernel)?,
+            kernel,
+            notify: None,
+        })
+    }
+
+    fn index(&self, name: &str) -> Result<usize> {
+        self.kernel.arg_index(name)
+    }
+
+    /// Sets scalar argument `index` to `value`.
+    pub fn set_arg<T>(&mut self, index: usize, value: T) -> Result<&mut Self>
+    where
+        T: Scalar,
+    {
+        self.run
+            .pin_mut()
+            .set_arg_scalar(index as i32, &value.to_bytes())?;
+        Ok(self)
+    }
+
+    /// Sets buffer argument `index` to `buffer`.
+    pub fn set_buffer(&mut self, index: usize, buffer: &Buffer) -> Result<&mut Self> {
+        self.run.pin_mut().set_arg_bo(index as i32, &buffer.bo)?;
+        Ok(self)
+    }
+
+    /// Sets scalar argument `name` to `value`.
+    pub fn set_arg_by_name<T>(&mut self, name: &str, value: T) -> Result<&mut Self>
+    where
+        T: Scalar,
+    {
+        let index = self.index(name)?;
+        self.set_arg(index, value)
+    }
+
+    /// Sets buffer argument `name` to `buffer`.
+    pub fn set_buffer_by_name(&mut self, name: &str, buffer: &Buffer) -> Result<&mut Self> {
+        let index = self.index(name)?;
+        self.set_buffer(index, buffer)
+    }
+
+    /// Starts the kernel with the current arguments.
+    pub fn start(&mut self) -> Result<()> {
+        Ok(self.run.pin_mut().start()?)
+    }
+
+    /// Starts the kernel with the current arguments, returning a future that
+    /// resolves when the run completes.
+    ///
+    /// Returns [Error::RunInFlight] if the previous start of this run is still
+    /// queued or running, as its completion would resolve the new future.
+    pub fn start_async(&mut self) -> Result<Completion<'_>> {
+        if self.in_flight() {
+            return Err(Error::RunInFlight);
+        }
+        let notify = match self.notify {
+            Some(ref notify) => Arc::clone(notify),
+            None => {
+                // The callback is registered once and invoked on every completion.
+                let notify = Arc::new(Mutex::new(Notify::default()));
+                self.run
+                    .pin_mut()
+                    .on_complete(complete, Arc::as_ptr(&notify) as usize)?;
+                self.notify.replace(Arc::clone(&notify));
+                notify
+            }
+        };
+        *notify.lock().unwrap() = Notify::default();
+        self.start()?;
+        Ok(Completion {
+            notify,
+            _run: PhantomData,
+        })
+    }
+
+    /// Waits for the run to finish, or for `timeout` to expire. Waits
+    /// indefinitely if `timeout` is `None`.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<RunState> {
+        Ok(RunState::from(self.run.wait_for(timeout_ms(timeout))?))
+    }
+
+    /// Returns the current state of the run.
+    pub fn state(&self) -> RunState {
+        RunState::from(self.run.cmd_state())
+    }
+
+    /// Returns true if this run was started and did not finish yet.
+    fn in_flight(&self) -> bool {
+        matches!(
+            self.state(),
+            RunState::Queued | RunState::Running | RunState::Submitted
+        )
+    }
+}
+
+impl Drop for Run<'_> {
+    fn drop(&mut self) {
+        // XRT keeps a started run alive until it completes, so the completion
+        // callback can fire after this run is dropped. Wait for an in-flight
+        // run to finish before `notify` is released.
+        if self.notify.is_some() && self.in_flight() {
+            let _ = self.run.wait_for(0);
+        }
+    }
+}
+
+impl Debug for Run<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Run").field("state", &self.state()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_state() {
+        assert_eq!(RunState::from(1), RunState::New);
+        assert_eq!(RunState::from(4), RunState::Completed);
+        assert_eq!(RunState::from(7), RunState::Submitted);
+        assert_eq!(RunState::from(9), RunState::NoResponse);
+        assert_eq!(RunState::from(0), RunState::Other(0));
+        assert_eq!(RunState::from(10), RunState::Other(10));
+
+        let finished = (1..=9)
+            .map(RunState::from)
+            .filter(|state| state.is_finished())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            finished,
+            [
+                RunState::Completed,
+                RunState::Error,
+                RunState::Abort,
+                RunState::Timeout,
+                RunState::NoResponse
+            ]
+        );
+        assert!(RunState::Other(10).is_finished());
+    }
+
+    #[test]
+    fn scalar() {
+        assert_eq!(0x12u8.to_bytes(), [0x12]);
+        assert_eq!(0x1234u16.to_bytes(), 0x1234u16.to_ne_bytes());
+        assert_eq!((-2i32).to_bytes(), (-2i32).to_ne_bytes());
+        assert_eq!(u64::MAX.to_bytes(), [0xff; 8]);
+        assert_eq!(1.5f32.to_bytes(), 1.5f32.to_ne_bytes());
+        assert_eq!(1.5f64.to_bytes().len(), 8);
+    }
+
+    #[test]
+    fn timeout() {
+        assert_eq!(timeout_ms(None), 0);
+        assert_eq!(timeout_ms(Some(Duration::from_secs(0))), 1);
+        assert_eq!(timeout_ms(Some(Duration::from_micros(1500))), 1);
+        assert_eq!(timeout_ms(Some(Duration::from_secs(2))), 2000);
+        assert_eq!(timeout_ms(Some(Duration::from_secs(u64::MAX))), u32::MAX);
+    }
+}
@@ -1,8 +1,102 @@
-use crate::ffi::{self, new_xclbin};
+use crate::{
+    ffi::{self, new_xclbin},
+    Error, Result,
+};
 use cxx::UniquePtr;
-use std::{fmt::Debug, fs, path::Path};
+use std::{convert::TryFrom, fmt::Debug, fs, io::Read, path::Path};
 use uuid::Uuid;
 
+/// A memory bank connected to a kernel argument.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemMetadata {
+    /// Index in the memory topology.
+    pub index: i32,
+    pub tag: String,
+    pub base_address: u64,
+    /// Size in bytes.
+    pub size: u64,
+    pub used: bool,
+}
+
+impl From<&ffi::XclbinMem> for MemMetadata {
+    fn from(mem: &ffi::XclbinMem) -> Self {
+        Self {
+            index: mem.index(),
+            tag: mem.tag(),
+            base_address: mem.base_address(),
+            size: mem.size_kb().saturating_mul(1024),
+            used: mem.used(),
+        }
+    }
+}
+
+/// An argument of a kernel or compute unit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArgMetadata {
+    pub index: usize,
+    pub name: String,
+    /// Offset of the argument in the register map of the compute unit.
+    pub offset: u64,
+    /// Size in bytes.
+    pub size: u64,
+    /// Type of the argument on the host, e.g. `int*`.
+    pub host_type: String,
+    /// Name of the port the argument is mapped to.
+    pub port: String,
+    /// Memory banks connected to the argument.
+    pub mems: Vec<MemMetadata>,
+}
+
+impl From<&ffi::XclbinArg> for ArgMetadata {
+    fn from(arg: &ffi::XclbinArg) -> Self {
+        Self {
+            index: arg.index(),
+            name: arg.name(),
+            offset: arg.offset(),
+            size: arg.size(),
+            host_type: arg.host_type(),
+            port: arg.port(),
+            mems: arg.mems().iter().map(MemMetadata::from).collect(),
+        }
+    }
+}
+
+/// An IP, e.g. a compute unit of a kernel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpMetadata {
+    pub name: String,
+    pub base_address: u64,
+    pub args: Vec<ArgMetadata>,
+}
+
+impl From<&ffi::XclbinIp> for IpMetadata {
+    fn from(ip: &ffi::XclbinIp) -> Self {
+        Self {
+            name: ip.name(),
+            base_address: ip.base_address(),
+            args: ip.args().iter().map(ArgMetadata::from).collect(),
+        }
+    }
+}
+
+/// A kernel and its compute units.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KernelMetadata {
+    pub name: String,
+    pub compute_units: Vec<IpMetadata>,
+    pub args: Vec<ArgMetadata>,
+}
+
+impl From<&ffi::XclbinKernel> for KernelMetadata {
+    fn from(kernel: &ffi::XclbinKernel) -> Self {
+        Self {
+            name: kernel.name(),
+            compute_units: kernel.cus().iter().map(IpMetadata::from).collect(),
+            args: kernel.args().iter().map(ArgMetadata::from).collect(),
+        }
+    }
+}
+
 pub struct Xclbin {
     pub(crate) xclbin: UniquePtr<ffi::Xclbin>,
 }
@@ -17,22 +111,45 @@ impl Debug for Xclbin {
 }
 
 impl Xclbin {
-    pub fn xsa_name(&self) -> &str {
+    /// Returns the name of the shell this xclbin targets.
+    pub fn xsa_name(&self) -> String {
         self.xclbin.xsa_name()
     }
 
-    pub fn kernels(&self) {
-        dbg!(self.xclbin.kernels().len());
-        // self.xclbin.kernels().iter().for_each(|kernel| {
-        //     println!("{}", kernel.name());
-        // });
+    /// Returns the interface UUID of the shell this xclbin targets. This is
+    /// nil for xclbins of shells without a partial reconfiguration interface.
+    pub fn interface_uuid(&self) -> Uuid {
+        Uuid::from_bytes(self.xclbin.interface_uuid())
+    }
+
+    /// Returns the kernels in this xclbin.
+    pub fn kernels(&self) -> impl Iterator<Item = KernelMetadata> {
+        self.xclbin
+            .kernels()
+            .iter()
+            .map(KernelMetadata::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the IPs in this xclbin.
+    pub fn ips(&self) -> impl Iterator<Item = IpMetadata> {
+        self.xclbin
+            .ips()
+            .iter()
+            .map(IpMetadata::from)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the kernel with `name`, if any.
+    pub fn kernel(&self, name: &str) -> Option<KernelMetadata> {
+        self.kernels().find(|kernel| kernel.name == name)
     }
 
-    pub fn ips(&self) {
-        dbg!(self.xclbin.ips().len());
-        // .iter().for_each(|ip| {
-        //     println!("{}", ip.name());
-        // });
+    /// Returns the IP with `name`, if any.
+    pub fn ip(&self, name: &str) -> Option<IpMetadata> {
+        self.ips().find(|ip| ip.name == name)
     }
 
     pub fn uuid(&self) -> Uuid {
@@ -41,22 +158,30 @@ impl Xclbin {
 }
 
 impl Xclbin {
-    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, std::io::Error> {
-        // Attempt to read the file.
-        let data = fs::read(path)?;
-
-        // Cast to i8, because that is what the C++ api expects.
-        // Prevent destructor.
-        let mut data = std::mem::ManuallyDrop::new(data);
-        // Cast.
-        // todo(mb): cast in c++ (don't know how without a copy)
-        let input = unsafe {
-            Vec::from_raw_parts(data.as_mut_ptr() as *mut i8, data.len(), data.capacity())
-        };
+    /// Parses the xclbin in `data`, e.g. embedded with `include_bytes!`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
         Ok(Self {
-            xclbin: new_xclbin(&input).map_err(|exception| {
-                std::io::Error::new(std::io::ErrorKind::InvalidData, exception.what())
-            })?,
+            xclbin: new_xclbin(data).map_err(Error::InvalidXclbin)?,
         })
     }
+
+    /// Reads and parses the xclbin from `reader`.
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::from_bytes(&data)
+    }
+
+    /// Reads and parses the xclbin file at `path`.
+    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+}
+
+impl TryFrom<&[u8]> for Xclbin {
+    type Error = Error;
+
+    fn try_from(data: &[u8]) -> Result<Self> {
+        Self::from_bytes(data)
+    }
 }
@@ -0,0 +1,143 @@
+use crate::{Context, DynPlatform, Error, PlatformType};
+use uuid::Uuid;
+
+#[cfg(feature = "opae")]
+use crate::opae::{self, DeviceProperties as _, ResourceProperties as _};
+
+#[cfg(feature = "xrt")]
+use crate::xrt;
+
+/// Describes a device found by [enumerate].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Descriptor {
+    /// The backend of this device.
+    pub platform: PlatformType,
+    /// Index of this device in the enumeration of its backend.
+    pub index: usize,
+    /// PCIe address of this device, formatted as `segment:bus:device.function`.
+    pub address: Option<String>,
+    /// Shell or model name of this device.
+    pub name: Option<String>,
+    /// UUID of the image loaded on this device.
+    pub uuid: Option<Uuid>,
+}
+
+impl Descriptor {
+    /// Opens the device described by this descriptor. Devices are selected by
+    /// their PCIe address when available, otherwise by their index.
+    pub fn open(&self) -> Result<Box<dyn DynPlatform>, Error> {
+        self.open_platform().map_err(|error| {
+            let error = error.with_operation("open");
+            match self.address {
+                Some(ref address) => error.with_device(address),
+                None => error.with_device(format!("{}:{}", self.platform, self.index)),
+            }
+        })
+    }
+
+    fn open_platform(&self) -> Result<Box<dyn DynPlatform>, Error> {
+        match self.platform {
+            #[cfg(feature = "xrt")]
+            PlatformType::XRT => Ok(Box::new(match self.address {
+                Some(ref address) => xrt::Xrt::from_bdf(address)?,
+                None => xrt::Xrt::from_device_index(self.index)?,
+            })),
+            #[cfg(feature = "opae")]
+            PlatformType::OPAE => {
+                let mut opae = match self.address {
+                    Some(ref address) => opae::Opae::from_filter(
+                        opae::Filter::new()
+                            .with_accelerator_object()
+                            .with_pci_address(address)?,
+                    )?,
+                    None => opae::Opae::enumerate()
+                        .into_iter()
+                        .nth(self.index)
+                        .ok_or_else(|| {
+                            opae::Error::from(opae::bindings::fpga_result::FPGA_NOT_FOUND)
+                        })?,
+                };
+                // Open the accelerator, so MMIO is available on the platform.
+                opae.open(opae::OpenMode::Shared)?;
+                Ok(Box::new(opae))
+            }
+            PlatformType::SIM => Err(Error::Unsupported(
+                Context::new().with_source("simulated devices are not enumerable"),
+            )),
+            platform => Err(Error::Unsupported(
+                Context::new().with_source(format!("{} backend is not enabled", platform)),
+            )),
+        }
+    }
+}
+
+/// Returns descriptors of all devices found by the enabled backends.
+pub fn enumerate() -> Vec<Descriptor> {
+    #[allow(unused_mut)]
+    let mut descriptors = Vec::new();
+
+    #[cfg(feature = "xrt")]
+    descriptors.extend(
+        xrt::Xrt::enumerate()
+            .iter()
+            .enumerate()
+            .map(|(index, xrt)| Descriptor {
+                platform: PlatformType::XRT,
+                index,
+                address: Some(xrt.bdf()),
+                name: Some(xrt.name()),
+                uuid: Some(xrt.xclbin_uuid()).filter(|uuid| !uuid.is_nil()),
+            }),
+    );
+
+    #[cfg(feature = "opae")]
+    descriptors.extend(
+        opae::Opae::enumerate()
+            .iter()
+            .enumerate()
+            .map(|(index, opae)| Descriptor {
+                platform: PlatformType::OPAE,
+                index,
+                address: opae.accelerator().pci_address().ok(),
+                name: opae.device().and_then(|device| device.model().ok()),
+                uuid: opae.accelerator().guid().ok(),
+            }),
+    );
+
+    descriptors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(address: Option<&str>) -> Descriptor {
+        Descriptor {
+            platform: PlatformType::SIM,
+            index: 1,
+            address: address.map(String::from),
+            name: None,
+            uuid: None,
+        }
+    }
+
+    #[test]
+    fn open_sim() {
+        let error = descriptor(None).open().err().unwrap();
+        assert!(matches!(error, Error::Unsupported(_)));
+        assert_eq!(error.context().operation.as_deref(), Some("open"));
+        assert_eq!(error.context().device.as_deref(), Some("sim:1"));
+        assert_eq!(
+            error.context().source.as_ref().unwrap().to_string(),
+            "simulated devices are not enumerable"
+        );
+        assert_eq!(error.to_string(), "unsupported during open on sim:1");
+    }
+
+    #[test]
+    fn open_address() {
+        let error = descriptor(Some("0000:3b:00.1")).open().err().unwrap();
+        assert_eq!(error.context().device.as_deref(), Some("0000:3b:00.1"));
+        assert_eq!(error.to_string(), "unsupported during open on 0000:3b:00.1");
+    }
+}
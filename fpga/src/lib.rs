@@ -1,20 +1,47 @@
+//! Platform-agnostic access to FPGA devices.
+//!
+//! Each backend is enabled with the feature of the same name. Devices of the
+//! `xrt` and `opae` backends are found with [discover] and [enumerate], and
+//! opened with [Descriptor::open].
+//!
+//! The `sim` feature only re-exports the simulated platform as `sim`. A
+//! simulated device is not backed by hardware, so it is never returned by
+//! [discover] or [enumerate], and must be constructed directly, e.g. with
+//! `sim::Sim::new()`.
+
 pub use fpga_core::*;
 
+#[cfg(feature = "hls")]
+pub use fpga_hls as hls;
+
 #[cfg(feature = "xrt")]
 pub use fpga_xrt as xrt;
 
 #[cfg(feature = "opae")]
 pub use fpga_opae as opae;
 
-pub fn discover() -> Option<Box<dyn Platform>> {
+/// The simulated platform. Construct a [sim::Sim] directly, as simulated
+/// devices are not enumerable.
+#[cfg(feature = "sim")]
+pub use fpga_sim as sim;
+
+mod descriptor;
+pub use descriptor::*;
+
+/// Returns the first platform found on this system, trying the enabled
+/// backends in order.
+pub fn discover() -> Option<Box<dyn DynPlatform>> {
     #[cfg(feature = "xrt")]
-    if let Some(platform) = xrt::Xrt::new().ok() {
-        return Some(Box::new(platform) as Box<dyn Platform>);
+    if let Ok(platform) = xrt::Xrt::new() {
+        return Some(Box::new(platform) as Box<dyn DynPlatform>);
     }
 
     #[cfg(feature = "opae")]
-    if let Some(platform) = opae::Opae::new().ok() {
-        return Some(Box::new(platform) as Box<dyn Platform>);
+    if let Ok(mut platform) = opae::Opae::new() {
+        // Open the accelerator, so MMIO is available on the platform. The
+        // platform is returned without MMIO if this fails.
+        platform.open(opae::OpenMode::Shared).ok();
+        return Some(Box::new(platform) as Box<dyn DynPlatform>);
     }
 
     None